@@ -9,11 +9,18 @@
 use std;
 
 use crate::{Extent, HasExtent};
+use crate::tokenizer::Token;
 use crate::visit::{Visit, Visitor, VisitorMut};
 
 /// An entire Rust file
 #[derive(Debug, Visit)]
 pub struct File {
+    /// A leading UTF-8 byte order mark
+    pub byte_order_mark: Option<Extent>,
+    /// A leading `#!` line, such as `#!/usr/bin/env run-cargo-script`
+    pub shebang: Option<Extent>,
+    /// Inner attributes and doc comments that precede the items
+    pub attributes: Vec<AttributeContaining>,
     pub items: Vec<Attributed<Item>>,
     pub whitespace: Vec<Whitespace>,
 }
@@ -28,13 +35,17 @@ pub enum Item {
     Function(Function),
     Impl(Impl),
     MacroCall(MacroCall),
+    MacroRules(MacroRules),
     Module(Module),
     Static(Static),
     Struct(Struct),
     Trait(Trait),
+    TraitAlias(TraitAlias),
     TypeAlias(TypeAlias),
     Use(Use),
     Union(Union),
+    /// Source that could not be parsed, see `parse_rust_file_with_recovery`
+    Error(Extent),
 }
 
 #[derive(Debug, HasExtent, ExtentIndex, Visit, Decompose)]
@@ -55,6 +66,10 @@ pub enum Attribute {
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct AttributeLiteral {
     pub extent: Extent,
+    /// The `unsafe` of an `unsafe(...)` wrapper around the contents
+    pub is_unsafe: Option<Extent>,
+    /// The structured contents, if they are in one of the standard forms
+    pub meta: Option<Meta>,
     pub text: Extent,
     pub whitespace: Vec<Whitespace>,
 }
@@ -77,10 +92,67 @@ pub enum AttributeContaining {
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct AttributeContainingLiteral {
     pub extent: Extent,
+    /// The `unsafe` of an `unsafe(...)` wrapper around the contents
+    pub is_unsafe: Option<Extent>,
+    /// The structured contents, if they are in one of the standard forms
+    pub meta: Option<Meta>,
     pub text: Extent,
     pub whitespace: Vec<Whitespace>,
 }
 
+/// The structured contents of an attribute
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// #[cfg(all(unix, feature = "x"))]
+/// //^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit, Decompose)]
+pub enum Meta {
+    Path(PathedIdent),
+    List(MetaList),
+    NameValue(MetaNameValue),
+}
+
+/// An attribute path followed by a parenthesized list
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// #[derive(Debug, Clone)]
+/// //^^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct MetaList {
+    pub extent: Extent,
+    pub path: PathedIdent,
+    pub members: Vec<MetaListMember>,
+    pub whitespace: Vec<Whitespace>,
+}
+
+#[derive(Debug, HasExtent, ExtentIndex, Visit, Decompose)]
+pub enum MetaListMember {
+    Meta(Meta),
+    Literal(Box<Attributed<Expression>>),
+}
+
+/// An attribute path with an assigned value
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// #[doc = "Hello"]
+/// //^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct MetaNameValue {
+    pub extent: Extent,
+    pub path: PathedIdent,
+    pub value: Box<Attributed<Expression>>,
+    pub whitespace: Vec<Whitespace>,
+}
+
 /// A lifetime identifier
 ///
 /// ### Example Source
@@ -271,23 +343,29 @@ pub struct TraitImplFunctionHeader {
     pub whitespace: Vec<Whitespace>,
 }
 
-/// Generic lifetime and type parameters
+/// Generic lifetime, type, and const parameters
 ///
 /// ### Example Source
 ///
 /// ```rust,ignore
-/// struct A<'a, T> {}
-/// //      ^^^^^^^
+/// struct A<'a, T, const N: usize> {}
+/// //      ^^^^^^^^^^^^^^^^^^^^^^^
 /// ```
 // TODO: rename to "parameters"?
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct GenericDeclarations {
     pub extent: Extent,
-    pub lifetimes: Vec<Attributed<GenericDeclarationLifetime>>,
-    pub types: Vec<Attributed<GenericDeclarationType>>,
+    pub members: Vec<Attributed<GenericDeclaration>>,
     pub whitespace: Vec<Whitespace>,
 }
 
+#[derive(Debug, HasExtent, ExtentIndex, Visit, Decompose)]
+pub enum GenericDeclaration {
+    Lifetime(GenericDeclarationLifetime),
+    Type(GenericDeclarationType),
+    Const(GenericDeclarationConst),
+}
+
 /// Generic lifetime parameters
 ///
 /// ### Example Source
@@ -321,6 +399,23 @@ pub struct GenericDeclarationType {
     pub whitespace: Vec<Whitespace>,
 }
 
+/// Generic const parameters
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// struct A<const N: usize, const M: u8 = 4> {}
+/// //       ^^^^^^^^^^^^^^  ^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct GenericDeclarationConst {
+    pub extent: Extent,
+    pub name: Ident,
+    pub typ: Type,
+    pub default: Option<Expression>,
+    pub whitespace: Vec<Whitespace>,
+}
+
 /// A concrete type
 ///
 /// ### Example Source
@@ -606,7 +701,10 @@ pub struct TypeGenericsAngle {
 pub enum TypeGenericsAngleMember {
     Lifetime(Lifetime),
     Type(Type),
-    AssociatedType(AssociatedType)
+    AssociatedType(AssociatedType),
+    AssociatedTypeBound(AssociatedTypeBound),
+    GenericAssociatedType(GenericAssociatedType),
+    Const(Expression),
 }
 
 /// An associated item in a type with generics
@@ -626,6 +724,39 @@ pub struct AssociatedType {
     pub whitespace: Vec<Whitespace>,
 }
 
+/// A bound on an associated item in a type with generics
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// fn a() -> impl Iterator<Item: Debug + Send> {}
+/// //                      ^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct AssociatedTypeBound {
+    pub extent: Extent,
+    pub name: Ident,
+    pub bounds: TraitBounds,
+    pub whitespace: Vec<Whitespace>,
+}
+
+/// A generic associated item in a type with generics
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// fn a<T: Lending<Item<'a> = &'a u8>>() {}
+/// //              ^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct GenericAssociatedType {
+    pub extent: Extent,
+    pub name: Ident,
+    pub generics: TypeGenericsAngle,
+    pub value: Type,
+    pub whitespace: Vec<Whitespace>,
+}
+
 /// A function pointer as a type
 ///
 /// ### Example Source
@@ -673,9 +804,27 @@ pub struct TypeFunctionArgumentNamed {
 /// fn main() {}
 /// // ^^^^
 /// ```
+///
+/// Raw identifiers keep the `r#` prefix in their extent:
+///
+/// ```rust,ignore
+/// fn r#match() {}
+/// // ^^^^^^^
+/// ```
 #[derive(Debug, Copy, Clone, HasExtent, ExtentIndex, Visit)]
 pub struct Ident {
     pub extent: Extent,
+    /// The identifier without any `'` or `r#` prefix
+    pub name: Extent,
+    /// If the identifier was written with the `r#` prefix
+    #[visit(ignore)]
+    pub is_raw: bool,
+}
+
+impl From<Extent> for Ident {
+    fn from(extent: Extent) -> Ident {
+        Ident { extent, name: extent, is_raw: false }
+    }
 }
 
 /// The path that an item is visible in
@@ -744,19 +893,18 @@ pub struct PathComponent {
     pub whitespace: Vec<Whitespace>,
 }
 
-/// Allows specifying concrete types
+/// Allows specifying concrete types and const values
 ///
 /// ### Example Source
 ///
 /// ```rust,ignore
-/// fn a() { None::<u8>; }
-/// //           ^^^^^^
+/// fn a() { None::<u8>; Buf::<{ N + 1 }>::new(); }
+/// //           ^^^^^^     ^^^^^^^^^^^^^
 /// ```
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct Turbofish {
     pub extent: Extent,
-    pub lifetimes: Vec<Lifetime>,
-    pub types: Vec<Type>,
+    pub members: Vec<TypeGenericsAngleMember>,
     pub whitespace: Vec<Whitespace>,
 }
 
@@ -1178,11 +1326,28 @@ pub enum TraitBoundType {
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct Block {
     pub extent: Extent,
+    pub attributes: Vec<AttributeContaining>,
     pub statements: Vec<Statement>,
     pub expression: Option<Attributed<Expression>>,
     pub whitespace: Vec<Whitespace>,
 }
 
+/// A block which can be exited early with a labeled `break`
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// fn a() { 'outer: { break 'outer 5; } }
+/// //       ^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct LabeledBlock {
+    pub extent: Extent,
+    pub label: Lifetime,
+    pub body: Box<Block>,
+    pub whitespace: Vec<Whitespace>,
+}
+
 /// A block which allows calling unsafe code
 ///
 /// ### Example Source
@@ -1214,6 +1379,21 @@ pub struct AsyncBlock {
     pub whitespace: Vec<Whitespace>,
 }
 
+/// A block which is evaluated at compile time
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// fn a() { const { 1 + 1 } }
+/// //       ^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct ConstBlock {
+    pub extent: Extent,
+    pub body: Box<Block>,
+    pub whitespace: Vec<Whitespace>,
+}
+
 /// An expression surrounded by parenthesis
 ///
 /// ### Example Source
@@ -1234,6 +1414,8 @@ pub enum Statement {
     Expression(Attributed<Expression>),
     Item(Attributed<Item>),
     Empty(Extent),
+    /// Source that could not be parsed, see `parse_rust_file_with_recovery`
+    Error(Extent),
 }
 
 /// An element that can have attributes applied to it.
@@ -1291,8 +1473,7 @@ macro_rules! visit_attributed {
 visit_attributed!(EnumVariant, visit_attributed_enum_variant, exit_attributed_enum_variant);
 visit_attributed!(Expression, visit_attributed_expression, exit_attributed_expression);
 visit_attributed!(ExternBlockMember, visit_attributed_extern_block_member, exit_attributed_extern_block_member);
-visit_attributed!(GenericDeclarationLifetime, visit_attributed_generic_declaration_lifetime, exit_attributed_generic_declaration_lifetime);
-visit_attributed!(GenericDeclarationType, visit_attributed_generic_declaration_type, exit_attributed_generic_declaration_type);
+visit_attributed!(GenericDeclaration, visit_attributed_generic_declaration, exit_attributed_generic_declaration);
 visit_attributed!(ImplMember, visit_attributed_impl_member, exit_attributed_impl_member);
 visit_attributed!(Item, visit_attributed_item, exit_attributed_item);
 visit_attributed!(StructDefinitionFieldNamed, visit_attributed_struct_definition_field_named, exit_attributed_struct_definition_field_named);
@@ -1317,22 +1498,25 @@ pub enum Expression {
     AsType(AsType),
     Ascription(Ascription),
     AsyncBlock(AsyncBlock),
+    Await(Await),
     Binary(Binary),
     Block(Box<Block>),
     Box(ExpressionBox),
     Break(Break),
     Byte(Byte),
     ByteString(ByteString),
+    CString(CString),
     Call(Call),
     Character(Character),
     Closure(Closure),
+    ConstBlock(ConstBlock),
     Continue(Continue),
     Dereference(Dereference),
     Disambiguation(Disambiguation),
     FieldAccess(FieldAccess),
     ForLoop(ForLoop),
     If(If),
-    IfLet(IfLet),
+    LabeledBlock(LabeledBlock),
     Let(Let),
     Loop(Loop),
     MacroCall(MacroCall),
@@ -1351,25 +1535,44 @@ pub enum Expression {
     UnsafeBlock(UnsafeBlock),
     Value(Value),
     While(While),
-    WhileLet(WhileLet),
 }
 
 impl Expression {
     pub(crate) fn may_terminate_statement(&self) -> bool {
         match *self {
-            Expression::Block(_)       |
-            Expression::ForLoop(_)     |
-            Expression::If(_)          |
-            Expression::IfLet(_)       |
-            Expression::Loop(_)        |
-            Expression::Match(_)       |
-            Expression::UnsafeBlock(_) |
-            Expression::While(_)       |
-            Expression::WhileLet(_)    |
+            Expression::Block(_)        |
+            Expression::ConstBlock(_)   |
+            Expression::ForLoop(_)      |
+            Expression::If(_)           |
+            Expression::LabeledBlock(_) |
+            Expression::Loop(_)         |
+            Expression::Match(_)        |
+            Expression::UnsafeBlock(_)  |
+            Expression::While(_)        |
             Expression::MacroCall(MacroCall { args: MacroCallArgs::Curly(_), .. }) => true,
             _ => false,
         }
     }
+
+    pub(crate) fn ends_with_curly_brace(&self) -> bool {
+        match *self {
+            ref e if e.may_terminate_statement() => true,
+            Expression::AsyncBlock(_) => true,
+            Expression::Value(Value { literal: Some(_), .. }) => true,
+
+            Expression::Binary(Binary { ref rhs, .. }) => rhs.ends_with_curly_brace(),
+            Expression::Box(ExpressionBox { ref target, .. }) => target.ends_with_curly_brace(),
+            Expression::Closure(Closure { ref body, .. }) => body.ends_with_curly_brace(),
+            Expression::Dereference(Dereference { ref target, .. }) => target.ends_with_curly_brace(),
+            Expression::Range(Range { rhs: Some(ref rhs), .. }) => rhs.ends_with_curly_brace(),
+            Expression::RangeInclusive(RangeInclusive { rhs: Some(ref rhs), .. }) => rhs.ends_with_curly_brace(),
+            Expression::Reference(Reference { ref target, .. }) => target.ends_with_curly_brace(),
+            Expression::Return(Return { value: Some(ref value), .. }) => value.ends_with_curly_brace(),
+            Expression::Break(Break { value: Some(ref value), .. }) => value.ends_with_curly_brace(),
+            Expression::Unary(Unary { ref value, .. }) => value.ends_with_curly_brace(),
+            _ => false,
+        }
+    }
 }
 
 /// A single unexpanded macro
@@ -1386,6 +1589,8 @@ pub struct MacroCall {
     pub name: PathedIdent,
     pub arg: Option<Ident>,
     pub args: MacroCallArgs,
+    /// The tokens inside the outermost delimiters of `args`
+    pub tokens: Vec<TokenTree>,
     pub whitespace: Vec<Whitespace>,
 }
 
@@ -1396,13 +1601,165 @@ pub enum MacroCallArgs {
     Square(Extent),
 }
 
+/// A declarative macro definition
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// mod m { macro_rules! a { ($b:expr) => { $b + 1 }; } }
+/// //      ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct MacroRules {
+    pub extent: Extent,
+    pub name: Ident,
+    pub rules: Vec<MacroRule>,
+    pub whitespace: Vec<Whitespace>,
+}
+
+/// A single arm of a declarative macro definition
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// macro_rules! a { ($b:expr) => { $b + 1 }; }
+/// //               ^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct MacroRule {
+    pub extent: Extent,
+    pub matcher: MacroMatcher,
+    pub transcriber: TokenTreeGroup,
+    pub whitespace: Vec<Whitespace>,
+}
+
+/// The delimited pattern of a declarative macro rule
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// macro_rules! a { ($b:expr) => { $b + 1 }; }
+/// //               ^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct MacroMatcher {
+    pub extent: Extent,
+    #[visit(ignore)]
+    pub delimiter: Delimiter,
+    pub matches: Vec<MacroMatch>,
+    pub whitespace: Vec<Whitespace>,
+}
+
+#[derive(Debug, HasExtent, ExtentIndex, Visit, Decompose)]
+pub enum MacroMatch {
+    Fragment(MacroFragment),
+    Group(MacroMatcher),
+    Repetition(MacroRepetition),
+    Token(TokenTreeLeaf),
+}
+
+/// A metavariable captured by a declarative macro
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// macro_rules! a { ($b:expr) => { $b + 1 }; }
+/// //                ^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct MacroFragment {
+    pub extent: Extent,
+    pub name: Ident,
+    pub specifier: Ident,
+    pub whitespace: Vec<Whitespace>,
+}
+
+/// A repeated sequence in a declarative macro matcher
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// macro_rules! a { ($($b:expr),*) => { 0 $(+ $b)* }; }
+/// //                 ^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct MacroRepetition {
+    pub extent: Extent,
+    pub matches: Vec<MacroMatch>,
+    pub separator: Option<TokenTreeLeaf>,
+    #[visit(ignore)]
+    pub operator: MacroRepetitionOperator,
+    pub whitespace: Vec<Whitespace>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MacroRepetitionOperator {
+    ZeroOrMore,
+    OneOrMore,
+    ZeroOrOne,
+}
+
+#[derive(Debug, HasExtent, ExtentIndex, Visit, Decompose)]
+pub enum TokenTree {
+    Group(TokenTreeGroup),
+    Leaf(TokenTreeLeaf),
+}
+
+/// A sequence of token trees surrounded by a pair of delimiters
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// fn a() { vec![(1, 2), (3, 4)]; }
+/// //            ^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct TokenTreeGroup {
+    pub extent: Extent,
+    #[visit(ignore)]
+    pub delimiter: Delimiter,
+    pub trees: Vec<TokenTree>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Delimiter {
+    Paren,
+    Curly,
+    Square,
+}
+
+/// A single token that is not a delimiter
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// fn a() { vec![a::b]; }
+/// //             ^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct TokenTreeLeaf {
+    pub extent: Extent,
+    #[visit(ignore)]
+    pub token: Token,
+    #[visit(ignore)]
+    pub spacing: Spacing,
+}
+
+/// If a punctuation token is immediately followed by another
+/// punctuation token, without any whitespace or comments between them
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Spacing {
+    Alone,
+    Joint,
+}
+
 /// A variable declaration
 ///
 /// ### Example Source
 ///
 /// ```rust,ignore
-/// fn a() { let b: u8 = 42; }
-/// //       ^^^^^^^^^^^^^^
+/// fn a() { let b: u8 = 42; let Some(c) = d else { return }; }
+/// //       ^^^^^^^^^^^^^^  ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
 /// ```
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct Let {
@@ -1410,6 +1767,7 @@ pub struct Let {
     pub pattern: Pattern,
     pub typ: Option<Type>,
     pub value: Option<Box<Attributed<Expression>>>,
+    pub else_block: Option<Box<Block>>,
     pub whitespace: Vec<Whitespace>,
 }
 
@@ -1443,6 +1801,21 @@ pub struct TryOperator {
     pub whitespace: Vec<Whitespace>,
 }
 
+/// Waiting for a future to complete
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// async fn a() { fut.await; }
+/// //             ^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct Await {
+    pub extent: Extent,
+    pub target: Box<Attributed<Expression>>,
+    pub whitespace: Vec<Whitespace>,
+}
+
 /// Access to a field of a struct
 ///
 /// ### Example Source
@@ -1656,53 +2029,21 @@ pub struct Loop {
     pub whitespace: Vec<Whitespace>,
 }
 
-/// The conditional, pattern-matching variable scope
-///
-/// ### Example Source
-///
-/// ```rust,ignore
-/// fn a() { if let Some(name) = current_player {} }
-/// //       ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-/// ```
-#[derive(Debug, HasExtent, ExtentIndex, Visit)]
-pub struct IfLet {
-    pub extent: Extent,
-    pub pattern: Pattern,
-    pub value: Box<Attributed<Expression>>,
-    pub body: Box<Block>,
-    pub whitespace: Vec<Whitespace>,
-}
-
 /// The boolean-based loop
 ///
-/// ### Example Source
-///
-/// ```rust,ignore
-/// fn a() { while players_count < 1 {} }
-/// //       ^^^^^^^^^^^^^^^^^^^^^^^^^^
-/// ```
-#[derive(Debug, HasExtent, ExtentIndex, Visit)]
-pub struct While {
-    pub extent: Extent,
-    pub label: Option<Lifetime>,
-    pub value: Box<Attributed<Expression>>,
-    pub body: Box<Block>,
-    pub whitespace: Vec<Whitespace>,
-}
-
-/// The conditional, pattern-matching loop
+/// The loop continues while every `let` in `value` matches, making
+/// `while let` a special case of a let-chain.
 ///
 /// ### Example Source
 ///
 /// ```rust,ignore
-/// fn a() { while let Some(i) = iterator.next() {} }
-/// //       ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// fn a() { while players_count < 1 {} while let Some(i) = iterator.next() {} }
+/// //       ^^^^^^^^^^^^^^^^^^^^^^^^^^ ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
 /// ```
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
-pub struct WhileLet {
+pub struct While {
     pub extent: Extent,
     pub label: Option<Lifetime>,
-    pub pattern: Pattern,
     pub value: Box<Attributed<Expression>>,
     pub body: Box<Block>,
     pub whitespace: Vec<Whitespace>,
@@ -1785,11 +2126,14 @@ pub enum BinaryOp {
 
 /// Boolean conditional control flow
 ///
+/// A `let` in the condition binds its pattern for the body. Several
+/// of them may be combined with other conditions using `&&`.
+///
 /// ### Example Source
 ///
 /// ```rust,ignore
-/// fn a() { if a {} else if b {} else {} }
-/// //       ^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// fn a() { if a {} else if let Some(b) = c && b {} else {} }
+/// //       ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
 /// ```
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct If {
@@ -1829,7 +2173,7 @@ pub struct Match {
 pub struct MatchArm {
     pub extent: Extent,
     pub attributes: Vec<Attribute>,
-    pub pattern: Vec<Pattern>,
+    pub pattern: Pattern,
     pub guard: Option<Attributed<Expression>>,
     pub hand: MatchHand,
     pub whitespace: Vec<Whitespace>,
@@ -2023,6 +2367,20 @@ pub struct ByteString {
     pub value: String,
 }
 
+/// A C string literal
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// fn a() { c"hello"; }
+/// //       ^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct CString {
+    pub extent: Extent,
+    pub value: String,
+}
+
 /// The square-bracket operator for slicing and indexing
 ///
 /// ### Example Source
@@ -2205,13 +2563,17 @@ pub struct PatternName {
 
 #[derive(Debug, HasExtent, ExtentIndex, Visit, Decompose)]
 pub enum PatternKind {
+    Alternation(PatternAlternation),
     Box(PatternBox),
     Byte(PatternByte),
     ByteString(PatternByteString),
+    CString(PatternCString),
     Character(PatternCharacter),
+    ConstBlock(PatternConstBlock),
     Ident(PatternIdent), // TODO: split into ident and enumtuple
     MacroCall(PatternMacroCall),
     Number(PatternNumber),
+    Parenthesized(PatternParenthesized),
     RangeExclusive(PatternRangeExclusive),
     RangeInclusive(PatternRangeInclusive),
     Reference(PatternReference),
@@ -2294,6 +2656,36 @@ pub struct PatternStructFieldShort {
     pub whitespace: Vec<Whitespace>,
 }
 
+/// Pattern matching any one of several alternatives
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// fn a() { let Ok(x) | Err(x) = r; }
+/// //           ^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct PatternAlternation {
+    pub extent: Extent,
+    pub patterns: Vec<Pattern>,
+    pub whitespace: Vec<Whitespace>,
+}
+
+/// A pattern surrounded by parenthesis
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// fn a() { let (Ok(x) | Err(x)) = r; }
+/// //           ^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct PatternParenthesized {
+    pub extent: Extent,
+    pub pattern: Box<Pattern>,
+    pub whitespace: Vec<Whitespace>,
+}
+
 /// Pattern matching a tuple
 ///
 /// ### Example Source
@@ -2342,8 +2734,8 @@ pub enum PatternSliceMember {
 /// ### Example Source
 ///
 /// ```rust,ignore
-/// fn a() { let [a, ref mut b..]; }
-/// //               ^^^^^^^^^^^
+/// fn a() { let [a, ref mut b @ ..]; }
+/// //               ^^^^^^^^^^^^^^
 /// ```
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct PatternSliceSubslice {
@@ -2351,6 +2743,8 @@ pub struct PatternSliceSubslice {
     pub is_ref: Option<Extent>,
     pub is_mut: Option<Extent>,
     pub name: Ident,
+    /// Absent for the legacy `name..` form
+    pub at: Option<Extent>,
     pub whitespace: Vec<Whitespace>,
 }
 
@@ -2396,6 +2790,34 @@ pub struct PatternByteString {
     pub value: ByteString,
 }
 
+/// Pattern matching a C string literal
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// fn a() { match x { c"abc" => {} } }
+/// //                 ^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct PatternCString {
+    pub extent: Extent,
+    pub value: CString,
+}
+
+/// Pattern matching the value of an inline constant
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// fn a() { let const { 1 + 1 } }
+/// //           ^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct PatternConstBlock {
+    pub extent: Extent,
+    pub value: ConstBlock,
+}
+
 /// Pattern matching a string literal
 ///
 /// ### Example Source
@@ -2448,11 +2870,13 @@ pub struct PatternMacroCall {
 /// fn a() { let 0..10; }
 /// //           ^^^^^
 /// ```
+///
+/// Either end may be omitted, but not both.
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct PatternRangeExclusive {
     pub extent: Extent,
-    pub start: PatternRangeComponent,
-    pub end: PatternRangeComponent,
+    pub start: Option<PatternRangeComponent>,
+    pub end: Option<PatternRangeComponent>,
     pub whitespace: Vec<Whitespace>,
 }
 
@@ -2464,10 +2888,12 @@ pub struct PatternRangeExclusive {
 /// fn a() { let 0..=10; }
 /// //           ^^^^^^
 /// ```
+///
+/// The start may be omitted.
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct PatternRangeInclusive {
     pub extent: Extent,
-    pub start: PatternRangeComponent,
+    pub start: Option<PatternRangeComponent>,
     #[visit(ignore)]
     pub operator: RangeInclusiveOperator,
     pub end: PatternRangeComponent,
@@ -2476,6 +2902,7 @@ pub struct PatternRangeInclusive {
 
 #[derive(Debug, HasExtent, ExtentIndex, Visit, Decompose)]
 pub enum PatternRangeComponent {
+    Disambiguation(Box<Disambiguation>),
     Ident(PathedIdent),
     Byte(Byte),
     Character(Character),
@@ -2513,6 +2940,25 @@ pub struct PatternBox {
     pub whitespace: Vec<Whitespace>,
 }
 
+/// Defines a new name for a combination of traits
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// mod m { pub trait Shape = Clone + Debug; }
+/// //      ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Visit)]
+pub struct TraitAlias {
+    pub extent: Extent,
+    pub visibility: Option<Visibility>,
+    pub name: Ident,
+    pub generics: Option<GenericDeclarations>,
+    pub bounds: TraitBounds,
+    pub wheres: Vec<Where>,
+    pub whitespace: Vec<Whitespace>,
+}
+
 /// Defines a trait
 ///
 /// ### Example Source
@@ -2530,6 +2976,7 @@ pub struct Trait {
     pub generics: Option<GenericDeclarations>,
     pub bounds: Option<TraitBounds>,
     pub wheres: Vec<Where>,
+    pub attributes: Vec<AttributeContaining>,
     pub members: Vec<Attributed<TraitMember>>,
     pub whitespace: Vec<Whitespace>,
 }
@@ -2566,12 +3013,22 @@ pub struct TraitMemberFunction {
 /// pub trait Iterator { type Item; fn next(&mut self) -> Option<Self::Item>; }
 /// //                   ^^^^^^^^^^
 /// ```
+///
+/// ```rust,ignore
+/// pub trait Lending { type Item<'a> where Self: 'a; }
+/// //                  ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct TraitMemberType {
     pub extent: Extent,
     pub name: Ident,
+    pub generics: Option<GenericDeclarations>,
     pub bounds: Option<TraitBounds>,
+    /// Where clauses before the default type
+    pub wheres: Vec<Where>,
     pub default: Option<Type>,
+    /// Where clauses after the default type
+    pub wheres_after: Vec<Where>,
     pub whitespace: Vec<Whitespace>,
 }
 
@@ -2606,6 +3063,7 @@ pub struct Impl {
     pub generics: Option<GenericDeclarations>,
     pub kind: ImplKind,
     pub wheres: Vec<Where>,
+    pub attributes: Vec<AttributeContaining>,
     pub body: Vec<Attributed<ImplMember>>,
     pub whitespace: Vec<Whitespace>,
 }
@@ -2686,11 +3144,21 @@ pub struct ImplFunction {
 /// impl Monster for Ogre { type Gold = u8; }
 /// //                      ^^^^^^^^^^^^^^^
 /// ```
+///
+/// ```rust,ignore
+/// impl<T> Lending for Vec<T> { type Item<'a> = &'a T where Self: 'a; }
+/// //                           ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct ImplType {
     pub extent: Extent,
     pub name: Ident,
+    pub generics: Option<GenericDeclarations>,
+    /// Where clauses before the `=`
+    pub wheres: Vec<Where>,
     pub typ: Type,
+    /// Where clauses after the type
+    pub wheres_after: Vec<Where>,
     pub whitespace: Vec<Whitespace>,
 }
 
@@ -2738,7 +3206,9 @@ pub struct Crate {
 #[derive(Debug, HasExtent, ExtentIndex, Visit)]
 pub struct ExternBlock {
     pub extent: Extent,
+    pub is_unsafe: Option<Extent>,
     pub abi: Option<String>,
+    pub attributes: Vec<AttributeContaining>,
     pub members: Vec<Attributed<ExternBlockMember>>,
     pub whitespace: Vec<Whitespace>,
 }
@@ -2762,12 +3232,28 @@ pub enum ExternBlockMember {
 pub struct ExternBlockMemberStatic {
     pub extent: Extent,
     pub visibility: Option<Visibility>,
+    #[visit(ignore)]
+    pub safety: Option<ExternSafety>,
     pub is_mut: Option<Extent>,
     pub name: Ident,
     pub typ: Type,
     pub whitespace: Vec<Whitespace>,
 }
 
+/// The safety qualifier of an item inside an `unsafe extern` block
+///
+/// ### Example Source
+///
+/// ```rust,ignore
+/// unsafe extern "C" { safe fn abs(v: i32) -> i32; }
+/// //                  ^^^^
+/// ```
+#[derive(Debug, HasExtent, ExtentIndex, Decompose)]
+pub enum ExternSafety {
+    Safe(Extent),
+    Unsafe(Extent),
+}
+
 /// An opaque FFI type
 ///
 /// ### Example Source
@@ -2796,6 +3282,8 @@ pub struct ExternBlockMemberType {
 pub struct ExternBlockMemberFunction {
     pub extent: Extent,
     pub visibility: Option<Visibility>,
+    #[visit(ignore)]
+    pub safety: Option<ExternSafety>,
     pub name: Ident,
     pub generics: Option<GenericDeclarations>,
     pub arguments: Vec<ExternBlockMemberFunctionArgument>,
@@ -2869,6 +3357,7 @@ pub struct Module {
     pub extent: Extent,
     pub visibility: Option<Visibility>,
     pub name: Ident,
+    pub attributes: Vec<AttributeContaining>,
     pub body: Option<Vec<Attributed<Item>>>,
     pub whitespace: Vec<Whitespace>,
 }
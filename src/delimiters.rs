@@ -0,0 +1,288 @@
+use std::fmt;
+
+use crate::{
+    Extent,
+    HumanTextError,
+    ast::Delimiter,
+    tokenizer::Token,
+};
+
+/// A problem with the balance of `()`, `[]`, and `{}`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DelimiterError {
+    /// An opening delimiter that is never closed.
+    ///
+    /// `likely_close` is a closing delimiter with the same
+    /// indentation as `open` that was used to close a more deeply
+    /// nested delimiter instead. That nested delimiter,
+    /// `likely_unclosed`, is probably the one missing its partner.
+    Unclosed { open: Extent, likely_close: Option<Extent>, likely_unclosed: Option<Extent> },
+    /// A closing delimiter that does not match the innermost opening
+    /// delimiter.
+    ///
+    /// `likely_open` is the enclosing opening delimiter that `close`
+    /// was most likely intended for, preferring one with the same
+    /// indentation.
+    Mismatched { open: Extent, close: Extent, likely_open: Option<Extent> },
+    /// A closing delimiter without any opening delimiter
+    Unopened { close: Extent },
+}
+
+impl DelimiterError {
+    /// Enhance the error with the source code
+    pub fn with_text<'a>(&'a self, text: &'a str) -> DelimiterErrorText<'a> {
+        DelimiterErrorText { error: self, text }
+    }
+
+    /// The byte offset into the source code of the offending delimiter
+    pub fn location(&self) -> usize {
+        match *self {
+            DelimiterError::Unclosed { open, .. } => open.0,
+            DelimiterError::Mismatched { close, .. } => close.0,
+            DelimiterError::Unopened { close } => close.0,
+        }
+    }
+}
+
+impl fmt::Display for DelimiterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DelimiterError::Unclosed { open, likely_close, likely_unclosed } => {
+                write!(f, "The delimiter at byte {} is never closed", open.0)?;
+                if let Some(close) = likely_close {
+                    write!(f, "; the delimiter at byte {} has the same indentation \
+                               and may have been intended to close it", close.0)?;
+                }
+                if let Some(unclosed) = likely_unclosed {
+                    write!(f, "; the delimiter at byte {} is probably the one missing \
+                               its partner", unclosed.0)?;
+                }
+                Ok(())
+            }
+            DelimiterError::Mismatched { open, close, likely_open } => {
+                write!(f, "The delimiter at byte {} does not close the delimiter at byte {}",
+                       close.0, open.0)?;
+                if let Some(likely_open) = likely_open {
+                    write!(f, "; it may have been intended to close the delimiter at byte {}",
+                           likely_open.0)?;
+                }
+                Ok(())
+            }
+            DelimiterError::Unopened { close } => {
+                write!(f, "The delimiter at byte {} has no opening delimiter", close.0)
+            }
+        }
+    }
+}
+
+/// A delimiter error including original source code
+#[derive(Debug)]
+pub struct DelimiterErrorText<'a> {
+    error: &'a DelimiterError,
+    text: &'a str,
+}
+
+impl<'a> DelimiterErrorText<'a> {
+    fn describe(&self, extent: Extent) -> String {
+        let human = HumanTextError::new(self.text, extent.0);
+        format!("`{}` (line {}, column {})", &self.text[extent], human.line, human.column)
+    }
+}
+
+impl<'a> fmt::Display for DelimiterErrorText<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self.error {
+            DelimiterError::Unclosed { open, likely_close, likely_unclosed } => {
+                write!(f, "The {} is never closed", self.describe(open))?;
+                if let Some(close) = likely_close {
+                    write!(f, "; the {} has the same indentation and may have been \
+                               intended to close it", self.describe(close))?;
+                }
+                if let Some(unclosed) = likely_unclosed {
+                    write!(f, "; the {} is probably the one missing its partner",
+                           self.describe(unclosed))?;
+                }
+                Ok(())
+            }
+            DelimiterError::Mismatched { open, close, likely_open } => {
+                write!(f, "The {} does not close the {}", self.describe(close), self.describe(open))?;
+                if let Some(likely_open) = likely_open {
+                    write!(f, "; it may have been intended to close the {}", self.describe(likely_open))?;
+                }
+                Ok(())
+            }
+            DelimiterError::Unopened { close } => {
+                write!(f, "The {} has no opening delimiter", self.describe(close))
+            }
+        }
+    }
+}
+
+/// Finds the unbalanced delimiters among the tokens, in source order
+pub(crate) fn check(text: &str, tokens: &[Token]) -> Vec<DelimiterError> {
+    let mut open: Vec<(Delimiter, Extent)> = Vec::new();
+    // Pairs that balance, even though their indentation disagrees
+    let mut misindented: Vec<(Extent, Extent)> = Vec::new();
+    let mut errors = Vec::new();
+
+    for token in tokens {
+        if let Some(delimiter) = opening_delimiter(token) {
+            open.push((delimiter, token.extent()));
+            continue;
+        }
+
+        let delimiter = match closing_delimiter(token) {
+            Some(delimiter) => delimiter,
+            None => continue,
+        };
+        let close = token.extent();
+
+        match open.last() {
+            Some(&(d, o)) if d == delimiter => {
+                open.pop();
+                if starts_line(text, close) && indentation(text, o) != indentation(text, close) {
+                    misindented.push((o, close));
+                }
+            }
+            Some(&(_, innermost)) => {
+                let candidates = || {
+                    open.iter().enumerate().rev().filter(|&(_, &(d, _))| d == delimiter)
+                };
+                let same_indentation = candidates()
+                    .find(|&(_, &(_, o))| indentation(text, o) == indentation(text, close));
+                let likely = same_indentation.or_else(|| candidates().next());
+
+                errors.push(DelimiterError::Mismatched {
+                    open: innermost,
+                    close,
+                    likely_open: likely.map(|(_, &(_, o))| o),
+                });
+
+                // Assume the delimiters nested inside of the likely
+                // opener are the ones that are missing a partner.
+                // Without one, the closer was probably a typo for the
+                // innermost opener's partner.
+                match likely {
+                    Some((i, _)) => open.truncate(i),
+                    None => { open.pop(); }
+                }
+            }
+            None => errors.push(DelimiterError::Unopened { close }),
+        }
+    }
+
+    for (_, o) in open {
+        let likely = misindented.iter()
+            .position(|&(nested, close)| {
+                nested.0 > o.0 && indentation(text, close) == indentation(text, o)
+            })
+            .map(|i| misindented.remove(i));
+
+        errors.push(DelimiterError::Unclosed {
+            open: o,
+            likely_close: likely.map(|(_, close)| close),
+            likely_unclosed: likely.map(|(nested, _)| nested),
+        });
+    }
+
+    errors.sort_by_key(DelimiterError::location);
+    errors
+}
+
+fn opening_delimiter(token: &Token) -> Option<Delimiter> {
+    match *token {
+        Token::LeftParen(_) => Some(Delimiter::Paren),
+        Token::LeftCurly(_) => Some(Delimiter::Curly),
+        Token::LeftSquare(_) => Some(Delimiter::Square),
+        _ => None,
+    }
+}
+
+fn closing_delimiter(token: &Token) -> Option<Delimiter> {
+    match *token {
+        Token::RightParen(_) => Some(Delimiter::Paren),
+        Token::RightCurly(_) => Some(Delimiter::Curly),
+        Token::RightSquare(_) => Some(Delimiter::Square),
+        _ => None,
+    }
+}
+
+fn line_start(text: &str, extent: Extent) -> usize {
+    text[..extent.0].rfind('\n').map_or(0, |i| i + 1)
+}
+
+fn indentation(text: &str, extent: Extent) -> usize {
+    let line = &text[line_start(text, extent)..];
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+fn starts_line(text: &str, extent: Extent) -> bool {
+    text[line_start(text, extent)..extent.0].trim().is_empty()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::check_delimiters;
+
+    #[test]
+    fn balanced() {
+        assert!(check_delimiters("fn a() { b[(c)] } // )").is_empty());
+    }
+
+    #[test]
+    fn unclosed() {
+        let e = check_delimiters("fn a() { b(");
+        assert_eq!(e, [
+            DelimiterError::Unclosed { open: Extent(7, 8), likely_close: None, likely_unclosed: None },
+            DelimiterError::Unclosed { open: Extent(10, 11), likely_close: None, likely_unclosed: None },
+        ]);
+    }
+
+    #[test]
+    fn unclosed_uses_indentation() {
+        let e = check_delimiters("fn a() {\n    if b {\n        c();\n}\n");
+        assert_eq!(e, [
+            DelimiterError::Unclosed {
+                open: Extent(7, 8), likely_close: Some(Extent(33, 34)), likely_unclosed: Some(Extent(18, 19)),
+            },
+        ]);
+    }
+
+    #[test]
+    fn unclosed_reports_likely_unclosed() {
+        let text = "fn a() {\n    if b {\n        c();\n}\n";
+        let e = check_delimiters(text);
+        assert_eq!(e[0].to_string(),
+                   "The delimiter at byte 7 is never closed; the delimiter at byte 33 \
+                    has the same indentation and may have been intended to close it; \
+                    the delimiter at byte 18 is probably the one missing its partner");
+        assert_eq!(e[0].with_text(text).to_string(),
+                   "The `{` (line 1, column 7) is never closed; the `}` (line 4, column 1) \
+                    has the same indentation and may have been intended to close it; \
+                    the `{` (line 2, column 10) is probably the one missing its partner");
+    }
+
+    #[test]
+    fn mismatched() {
+        let e = check_delimiters("fn a() { (1, 2]; }");
+        assert_eq!(e, [
+            DelimiterError::Mismatched { open: Extent(9, 10), close: Extent(14, 15), likely_open: None },
+        ]);
+    }
+
+    #[test]
+    fn mismatched_uses_indentation() {
+        let e = check_delimiters("fn a() {\n    [{(b\n}\n");
+        assert_eq!(e, [
+            DelimiterError::Mismatched { open: Extent(15, 16), close: Extent(18, 19), likely_open: Some(Extent(7, 8)) },
+        ]);
+    }
+
+    #[test]
+    fn unopened() {
+        let e = check_delimiters("fn a() {} }");
+        assert_eq!(e, [DelimiterError::Unopened { close: Extent(10, 11) }]);
+    }
+}
@@ -110,18 +110,114 @@
 //! }
 //! ```
 //!
-//! This produces an error that shows the offending location and what
-//! possible symbols were expected. We've truncated this output:
+//! This produces an error that shows the offending location, the
+//! innermost part of the grammar being parsed, what was expected, and
+//! any unbalanced delimiter that is likely to blame:
 //!
 //! ```text
-//! Unable to parse text (line 2, column 14)
+//! Unable to parse text (line 2, column 14) while parsing function arguments of `main`
 //!
 //!     fn main( { let the_variable_name = 1 + 1; }
 //!              ^
 //! Expected:
-//!   ExpectedAmpersand
-//!   ExpectedBox
-//!   ...
+//!   a pattern
+//!   `)`
+//! The `(` (line 2, column 12) is never closed
+//! ```
+//!
+//! The error kinds are also available to build your own diagnostics.
+//!
+//! ```
+//! extern crate fuzzy_pickles;
+//!
+//! use fuzzy_pickles::{parse_rust_file, parser, ErrorDetail};
+//!
+//! fn main() {
+//!     let example_source = "use a::{b c};";
+//!
+//!     match parse_rust_file(example_source).unwrap_err() {
+//!         ErrorDetail::Parser(e) => {
+//!             assert_eq!(e.location(), 10);
+//!             assert!(e.expected().contains(&parser::Error::ExpectedRightCurly));
+//!         }
+//!         ErrorDetail::Tokenizer(e) => panic!("Unexpected tokenizer error: {}", e),
+//!     }
+//! }
+//! ```
+//!
+//! Unbalanced delimiters can also be found without parsing. When
+//! indentation suggests which delimiters were meant to be paired,
+//! that pairing is reported as well.
+//!
+//! ```
+//! extern crate fuzzy_pickles;
+//!
+//! use fuzzy_pickles::{check_delimiters, delimiters::DelimiterError};
+//!
+//! fn main() {
+//!     let example_source = "fn main() {\n    if a {\n        b();\n\n    c();\n}\n";
+//!
+//!     match check_delimiters(example_source)[..] {
+//!         [DelimiterError::Unclosed { open, likely_close: Some(close), likely_unclosed: Some(unclosed) }] => {
+//!             assert_eq!(open.0, 10);
+//!             assert_eq!(close.0, 46);
+//!             assert_eq!(unclosed.0, 21);
+//!         }
+//!         ref e => panic!("Unexpected delimiter errors: {:?}", e),
+//!     }
+//! }
+//! ```
+//!
+//! To keep going past malformed code, [`parse_rust_file_with_recovery`]
+//! replaces each malformed item or statement with an `Error` node and
+//! returns every error alongside the partial file.
+//!
+//! ```
+//! extern crate fuzzy_pickles;
+//!
+//! use fuzzy_pickles::parse_rust_file_with_recovery;
+//!
+//! fn main() {
+//!     let example_source = r#"
+//!     fn main() { let = 1 + 1; }
+//!     struct { }
+//!     fn other() {}
+//!     "#;
+//!
+//!     let (file, errors) = parse_rust_file_with_recovery(example_source);
+//!     assert_eq!(errors.len(), 2);
+//!
+//!     let main_fn = file.items[0].value.as_function()
+//!         .expect("Not a function");
+//!     assert!(main_fn.body.statements[0].is_error());
+//!
+//!     assert!(file.items[1].value.is_error());
+//!     assert!(file.items[2].value.is_function());
+//! }
+//! ```
+//!
+//! ## Parsing fragments
+//!
+//! Smaller pieces of code, such as a single expression or type, can
+//! be parsed on their own. The entire input must be consumed.
+//!
+//! ```
+//! extern crate fuzzy_pickles;
+//!
+//! use fuzzy_pickles::parse_expression;
+//!
+//! fn main() {
+//!     let example_source = "a.len() + 1";
+//!
+//!     let expr = parse_expression(example_source)
+//!         .expect("Unable to parse source");
+//!
+//!     let addition_expr = expr.value.as_binary()
+//!         .expect("Not a binary expression");
+//!     assert_eq!("a.len()", &example_source[addition_expr.lhs.extent]);
+//!
+//!     assert!(parse_expression("a.len() +").is_err());
+//! }
 //! ```
 //!
 
@@ -144,6 +240,7 @@ pub mod ast;
 pub mod tokenizer;
 pub mod visit;
 pub mod parser;
+pub mod delimiters;
 
 use std::fmt;
 use crate::whitespace_apportioner::WhitespaceApportioner;
@@ -222,8 +319,27 @@ impl ErrorDetail {
     pub fn with_text<'a>(&'a self, text: &'a str) -> ErrorDetailText<'a> {
         ErrorDetailText { detail: self, text }
     }
+
+    /// The byte offset into the source code where the error occurred
+    pub fn location(&self) -> usize {
+        match *self {
+            ErrorDetail::Tokenizer(ref t) => t.location(),
+            ErrorDetail::Parser(ref p) => p.location(),
+        }
+    }
 }
 
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ErrorDetail::Tokenizer(ref t) => t.fmt(f),
+            ErrorDetail::Parser(ref p) => p.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ErrorDetail {}
+
 impl From<tokenizer::ErrorDetail> for ErrorDetail {
     fn from(other: tokenizer::ErrorDetail) -> Self {
         ErrorDetail::Tokenizer(other)
@@ -275,14 +391,62 @@ impl<'a> HumanTextError<'a> {
     }
 }
 
-fn extract_whitespace(file: &str) -> Result<(WhitespaceApportioner, Vec<tokenizer::Token>), tokenizer::ErrorDetail> {
-    use crate::tokenizer::{Token, Tokens};
+// Lists the alternatives as `a`, `b`, or `c`
+fn write_expected<I>(f: &mut fmt::Formatter, expected: I) -> fmt::Result
+    where I: IntoIterator,
+          I::Item: fmt::Display,
+          I::IntoIter: ExactSizeIterator,
+{
+    let expected = expected.into_iter();
+    let count = expected.len();
+
+    for (i, e) in expected.enumerate() {
+        match i {
+            0 => {}
+            _ if i + 1 == count && count == 2 => f.write_str(" or ")?,
+            _ if i + 1 == count => f.write_str(", or ")?,
+            _ => f.write_str(", ")?,
+        }
+        write!(f, "{}", e)?;
+    }
+    Ok(())
+}
+
+struct FilePrefix {
+    byte_order_mark: Option<Extent>,
+    shebang: Option<Extent>,
+}
+
+type Extracted = (WhitespaceApportioner, FilePrefix, Vec<tokenizer::Token>);
+
+fn extract_whitespace(tokens_iter: tokenizer::Tokens) -> Result<Extracted, tokenizer::ErrorDetail> {
+    match extract_whitespace_until_error(tokens_iter) {
+        (_, Some(e)) => Err(e),
+        (extracted, None) => Ok(extracted),
+    }
+}
+
+// Keeps everything before a tokenizer error, ending the tokens with
+// an end of file where the failing token starts.
+fn extract_whitespace_until_error(tokens_iter: tokenizer::Tokens) ->
+    (Extracted, Option<tokenizer::ErrorDetail>)
+{
+    use crate::tokenizer::Token;
 
     let mut ws = WhitespaceApportioner::default();
+    let mut prefix = FilePrefix { byte_order_mark: None, shebang: None };
     let mut tokens = Vec::new();
+    let mut end = 0;
 
-    for token in Tokens::new(file) {
-        let token = token?;
+    for token in tokens_iter {
+        let token = match token {
+            Ok(token) => token,
+            Err(e) => {
+                tokens.push(Token::EndOfFile(Extent(end, end)));
+                return ((ws, prefix, tokens), Some(e));
+            }
+        };
+        end = token.extent().1;
 
         match token {
             Token::Whitespace(w) => {
@@ -296,26 +460,72 @@ fn extract_whitespace(file: &str) -> Result<(WhitespaceApportioner, Vec<tokenize
                 let c = ast::Comment::Block(c);
                 ws.push(ast::Whitespace::Comment(c));
             }
+            Token::ByteOrderMark(b) => prefix.byte_order_mark = Some(b),
+            Token::Shebang(s) => prefix.shebang = Some(s),
             o => tokens.push(o),
         }
     }
 
-    Ok((ws, tokens))
+    ((ws, prefix, tokens), None)
 }
 
 /// The entrypoint to parsing Rust code.
 pub fn parse_rust_file(file: &str) -> Result<ast::File, ErrorDetail> {
+    parse_file(file, false).map(|(file, _)| file)
+}
+
+/// Parses Rust code, continuing past any malformed items and
+/// statements.
+///
+/// Each top-level item that fails to parse is replaced by an
+/// `Item::Error` and each statement inside of a block by a
+/// `Statement::Error`, both covering the skipped source. The errors
+/// for all of the skipped source are returned in source order.
+///
+/// Tokenizing cannot be resumed after an error, such as an
+/// unterminated string. The source before it is parsed as usual and
+/// the rest of the file becomes a single `Item::Error`.
+pub fn parse_rust_file_with_recovery(file: &str) -> (ast::File, Vec<ErrorDetail>) {
+    match parse_file(file, true) {
+        Ok(parsed) => parsed,
+        // When recovering, every error is collected alongside the
+        // partial file instead of being returned
+        Err(_) => unreachable!("Recovering from errors cannot fail"),
+    }
+}
+
+fn parse_file(file: &str, recover: bool) -> Result<(ast::File, Vec<ErrorDetail>), ErrorDetail> {
     use crate::{
-        parser::{attributed, item, Point, Master, State},
+        parser::{attributed, file_attributes, item, skip_malformed, Point, Master, State},
         tokenizer::Token,
         visit::Visit,
     };
 
-    let (mut ws, tokens) = extract_whitespace(file)?;
+    let ((mut ws, prefix, tokens), tokenizer_error) =
+        extract_whitespace_until_error(tokenizer::Tokens::new(file));
+    let tokenizer_error = match tokenizer_error {
+        Some(e) if !recover => return Err(e.into()),
+        e => e,
+    };
+
+    let state = if recover { State::with_statement_recovery(file) } else { State::new(file) };
 
     let mut pt = Point::new(&tokens);
-    let mut pm = Master::with_state(State::new());
+    let mut pm = Master::with_state(state);
     let mut items = Vec::new();
+    let mut errors = Vec::new();
+    // Only needed to explain a failure
+    let mut delimiter_errors = None;
+
+    // A malformed attribute is reported when parsing it as an item
+    let attributes = file_attributes(&mut pm, pt);
+    let attributes = match pm.finish(attributes) {
+        peresil::Progress { status: peresil::Status::Success(a), point } => {
+            pt = point;
+            a
+        }
+        peresil::Progress { status: peresil::Status::Failure(_), .. } => Vec::new(),
+    };
 
     loop {
         if pt.s.first().map(Token::is_end_of_file).unwrap_or(true) { break }
@@ -325,14 +535,37 @@ pub fn parse_rust_file(file: &str) -> Result<ast::File, ErrorDetail> {
 
         let next_pt = match item.status {
             peresil::Status::Success(s) => {
+                for (extent, mut detail) in statement_errors(&s, pm.state.take_statement_errors()) {
+                    let unbalanced = delimiter_errors
+                        .get_or_insert_with(|| delimiters::check(file, &tokens));
+                    detail.delimiter = delimiter_within(unbalanced, extent).map(Box::new);
+                    errors.push(ErrorDetail::Parser(detail));
+                }
                 items.push(s);
                 item.point
             },
             peresil::Status::Failure(e) => {
-                return Err(ErrorDetail::Parser(parser::ErrorDetail {
-                    location: tokens[item.point.offset].extent().0,
-                    errors: e.into_iter().collect(),
-                }))
+                let next_pt = skip_malformed(pt, false);
+                let extent = pm.state.ex(pt, next_pt);
+
+                let mut detail = pm.state.error_detail(item.point, e);
+                let unbalanced = delimiter_errors
+                    .get_or_insert_with(|| delimiters::check(file, &tokens));
+                detail.delimiter = delimiter_within(unbalanced, extent).map(Box::new);
+                let detail = ErrorDetail::Parser(detail);
+
+                if !recover { return Err(detail) }
+
+                errors.push(detail);
+                pm.state.take_statement_errors();
+
+                items.push(ast::Attributed {
+                    extent,
+                    attributes: Vec::new(),
+                    value: ast::Item::Error(extent),
+                    whitespace: Vec::new(),
+                });
+                next_pt
             },
         };
 
@@ -340,12 +573,143 @@ pub fn parse_rust_file(file: &str) -> Result<ast::File, ErrorDetail> {
         pt = next_pt;
     }
 
-    let mut file = ast::File { items, whitespace: Vec::new() };
+    if let Some(e) = tokenizer_error {
+        let start = tokens.last().map_or(0, |t| t.extent().0);
+        let extent = Extent(start, file.len());
+        items.push(ast::Attributed {
+            extent,
+            attributes: Vec::new(),
+            value: ast::Item::Error(extent),
+            whitespace: Vec::new(),
+        });
+        errors.push(e.into());
+    }
+
+    let FilePrefix { byte_order_mark, shebang } = prefix;
+    let mut file = ast::File { byte_order_mark, shebang, attributes, items, whitespace: Vec::new() };
 
     file.visit_mut(&mut ws);
     assert!(ws.is_empty(), "Did not assign all whitespace");
 
-    Ok(file)
+    Ok((file, errors))
+}
+
+// Blocks may be parsed multiple times while backtracking, so only
+// keep the errors of the statements that made it into the item.
+fn statement_errors<T>(node: &T, recorded: Vec<(Extent, parser::ErrorDetail)>) -> Vec<(Extent, parser::ErrorDetail)>
+    where T: visit::Visit
+{
+    use crate::visit::{Control, Visitor};
+
+    struct ErrorStatements(Vec<Extent>);
+
+    impl<'ast> Visitor<'ast> for ErrorStatements {
+        fn visit_statement(&mut self, s: &'ast ast::Statement) -> Control {
+            if let ast::Statement::Error(extent) = *s {
+                self.0.push(extent);
+            }
+            Control::Continue
+        }
+    }
+
+    let mut v = ErrorStatements(Vec::new());
+    node.visit(&mut v);
+
+    let mut errors: Vec<_> = recorded.into_iter()
+        .filter(|(extent, _)| v.0.contains(extent))
+        .collect();
+    errors.sort_by_key(|(extent, _)| *extent);
+    errors.dedup_by_key(|(extent, _)| *extent);
+    errors
+}
+
+// The first unbalanced delimiter within the malformed code is the
+// most likely explanation for it.
+fn delimiter_within(unbalanced: &[delimiters::DelimiterError], extent: Extent) -> Option<delimiters::DelimiterError> {
+    unbalanced.iter()
+        .find(|d| extent.0 <= d.location() && d.location() < extent.1)
+        .cloned()
+}
+
+/// Finds the delimiters that are unclosed, unopened, or closed by the
+/// wrong kind of delimiter.
+///
+/// Source that cannot be tokenized is only checked up to the
+/// tokenizer error.
+pub fn check_delimiters(file: &str) -> Vec<delimiters::DelimiterError> {
+    use crate::tokenizer::Tokens;
+
+    let tokens: Vec<_> = Tokens::new(file)
+        .take_while(Result::is_ok)
+        .filter_map(Result::ok)
+        .filter(|t| !t.is_whitespace() && !t.is_comment_line() && !t.is_comment_block())
+        .collect();
+
+    delimiters::check(file, &tokens)
+}
+
+/// Parses a single expression, such as `a + b`.
+pub fn parse_expression(text: &str) -> Result<ast::Attributed<ast::Expression>, ErrorDetail> {
+    parse_fragment(text, parser::expression)
+}
+
+/// Parses a single type, such as `Vec<u8>`.
+pub fn parse_type(text: &str) -> Result<ast::Type, ErrorDetail> {
+    parse_fragment(text, parser::typ)
+}
+
+/// Parses a single pattern, such as `Some(a) | None`.
+pub fn parse_pattern(text: &str) -> Result<ast::Pattern, ErrorDetail> {
+    parse_fragment(text, parser::pattern)
+}
+
+/// Parses a single item along with its attributes, such as
+/// `#[derive(Debug)] struct Point;`.
+pub fn parse_item(text: &str) -> Result<ast::Attributed<ast::Item>, ErrorDetail> {
+    parse_fragment(text, |pm, pt| parser::attributed(parser::item)(pm, pt))
+}
+
+/// Parses a single statement, such as `let a = 1;`. The trailing
+/// semicolon is optional.
+pub fn parse_statement(text: &str) -> Result<ast::Statement, ErrorDetail> {
+    parse_fragment(text, parser::statement_alone)
+}
+
+/// Parses a single block, such as `{ a(); b(); }`.
+pub fn parse_block(text: &str) -> Result<ast::Block, ErrorDetail> {
+    parse_fragment(text, parser::block)
+}
+
+fn parse_fragment<F, T>(text: &str, f: F) -> Result<T, ErrorDetail>
+    where F: for<'s> FnOnce(&mut parser::Master<'s>, parser::Point<'s>) -> parser::Progress<'s, T>,
+          T: HasExtent + visit::Visit,
+{
+    use crate::parser::{fragment, Point, Master, State};
+
+    // A fragment never starts a file, so it has no prefix
+    let (mut ws, _prefix, tokens) = extract_whitespace(tokenizer::Tokens::fragment(text))?;
+
+    let pt = Point::new(&tokens);
+    let mut pm = Master::with_state(State::new(text));
+
+    let r = fragment(f)(&mut pm, pt);
+    let r = pm.finish(r);
+
+    let mut value = match r.status {
+        peresil::Status::Success(v) => v,
+        peresil::Status::Failure(e) => {
+            let mut detail = pm.state.error_detail(r.point, e);
+            detail.delimiter = delimiters::check(text, &tokens).first().cloned().map(Box::new);
+            return Err(ErrorDetail::Parser(detail))
+        }
+    };
+
+    // Whitespace surrounding the fragment has no node to belong to
+    ws.retain_within(value.extent());
+    value.visit_mut(&mut ws);
+    assert!(ws.is_empty(), "Did not assign all whitespace");
+
+    Ok(value)
 }
 
 #[cfg(test)]
@@ -358,6 +722,218 @@ mod test {
         assert!(r.is_ok());
     }
 
+    #[test]
+    fn error_on_invalid_fragment_specifier() {
+        let r = parse_rust_file("macro_rules! a { ($b:expr, $c:exp) => {} }");
+        let e = r.expect_err("Parsing should fail");
+        match e {
+            ErrorDetail::Parser(e) => {
+                assert_eq!(e.location, 30);
+                assert!(e.errors.contains(&parser::Error::InvalidFragmentSpecifier));
+            }
+            other => panic!("Expected a parser error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn can_parse_a_file_with_a_shebang() {
+        let r = parse_rust_file("#!/usr/bin/env run-cargo-script\nfn main() {}");
+        let file = r.expect("Unable to parse");
+        assert_eq!(file.shebang, Some(Extent(0, 31)));
+        assert_eq!(file.items.len(), 1);
+    }
+
+    #[test]
+    fn inner_attribute_is_not_a_shebang() {
+        let r = parse_rust_file("#! /* hi */ [allow(dead_code)]\nfn main() {}");
+        let file = r.expect("Unable to parse");
+        assert_eq!(file.shebang, None);
+        assert_eq!(file.attributes.len(), 1);
+        assert_eq!(file.items.len(), 1);
+    }
+
+    #[test]
+    fn can_parse_a_file_with_a_byte_order_mark() {
+        let r = parse_rust_file("\u{feff}#!shebang\nfn main() {}");
+        let file = r.expect("Unable to parse");
+        assert_eq!(file.byte_order_mark, Some(Extent(0, 3)));
+        assert_eq!(file.shebang, Some(Extent(3, 12)));
+    }
+
+    #[test]
+    fn parser_error_is_human_readable() {
+        let e = parse_rust_file("use a::{b c};").expect_err("Parsing should fail");
+        assert_eq!(e.location(), 10);
+        assert_eq!(e.to_string(), "Unable to parse text at byte 10, expected `::` or `}`");
+    }
+
+    #[test]
+    fn tokenizer_error_is_human_readable() {
+        let e = parse_rust_file("/* a").expect_err("Tokenizing should fail");
+        assert_eq!(e.location(), 0);
+        assert_eq!(e.to_string(), "Unable to tokenize text at byte 0, expected the end of the block comment");
+    }
+
+    #[test]
+    fn raw_path_keywords_are_tokenizer_errors() {
+        for &(code, location) in &[("fn r#crate() {}", 5), ("fn a() { let r#_ = 1; }", 15)] {
+            match parse_rust_file(code) {
+                Err(ErrorDetail::Tokenizer(e)) => assert_eq!(e.location(), location, "{}", code),
+                other => panic!("Expected a tokenizer error for {}, got {:?}", code, other),
+            }
+        }
+    }
+
+    #[test]
+    fn error_with_text_lists_expected_items() {
+        let text = "use a::{b c};";
+        let e = parse_rust_file(text).expect_err("Parsing should fail");
+        let pretty = e.with_text(text).to_string();
+        assert!(pretty.contains("Expected:\n  `::`\n  `}`\n"), "{}", pretty);
+    }
+
+    fn parser_error(text: &str) -> parser::ErrorDetail {
+        match parse_rust_file(text).expect_err("Parsing should fail") {
+            ErrorDetail::Parser(e) => e,
+            other => panic!("Expected a parser error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn error_groups_expected_expression() {
+        let e = parser_error("fn a() { foo(1, }");
+        assert_eq!(e.expected_summary(), [
+            parser::Expected::Expression,
+            parser::Expected::Error(parser::Error::ExpectedRightParen),
+        ]);
+    }
+
+    #[test]
+    fn error_groups_expected_pattern() {
+        let e = parser_error("fn a() { let | = 1; }");
+        assert_eq!(e.expected_summary(), [parser::Expected::Pattern]);
+    }
+
+    #[test]
+    fn error_groups_expected_operator() {
+        let e = parser_error("fn a() { b(1 2) }");
+        let summary = e.expected_summary();
+        assert_eq!(summary[0], parser::Expected::Operator);
+        assert!(!summary.contains(&parser::Expected::Error(parser::Error::ExpectedPlus)));
+    }
+
+    #[test]
+    fn error_groups_closing_delimiters() {
+        let e = parser::ErrorDetail {
+            location: 0,
+            errors: [parser::Error::ExpectedRightParen, parser::Error::ExpectedRightSquare].into(),
+            context: None,
+            delimiter: None,
+        };
+        assert_eq!(e.expected_summary(), [parser::Expected::ClosingDelimiter]);
+    }
+
+    #[test]
+    fn error_reports_innermost_production() {
+        let text = "fn main( { }";
+        let e = parser_error(text);
+        let context = e.context().expect("No context");
+        assert_eq!(context.production(), parser::Production::FunctionArguments);
+        assert_eq!(context.name(), Some(Extent(3, 7)));
+
+        let pretty = e.with_text(text).to_string();
+        assert!(pretty.starts_with("Unable to parse text (line 1, column 9) \
+                                    while parsing function arguments of `main`\n"), "{}", pretty);
+    }
+
+    #[test]
+    fn error_reports_nested_production() {
+        let e = parser_error("fn main() { a(b, |c d| c); }");
+        let context = e.context().expect("No context");
+        assert_eq!(context.production(), parser::Production::ClosureArguments);
+    }
+
+    #[test]
+    fn error_reports_production_for_direct_failure() {
+        let e = parser_error("fn main() { a(if let b = c || d {}); }");
+        assert_eq!(e.location(), 17);
+        let context = e.context().expect("No context");
+        assert_eq!(context.production(), parser::Production::CallArguments);
+    }
+
+    #[test]
+    fn error_reports_named_impl_body() {
+        let e = parser_error("impl A for B { type C = ; }");
+        let context = e.context().expect("No context");
+        assert_eq!(context.production(), parser::Production::ImplBody);
+        assert_eq!(context.name(), Some(Extent(5, 12)));
+    }
+
+    #[test]
+    fn error_ignores_production_that_made_no_progress() {
+        let e = parser_error("struct A { b: u8 } fn main {}");
+        assert_eq!(e.context(), None);
+        assert_eq!(e.to_string(), "Unable to parse text at byte 27, expected `(`");
+    }
+
+    #[test]
+    fn recovered_statement_error_reports_production() {
+        let (_, errors) = parse_rust_file_with_recovery("fn a() { b(1 2); }");
+        match errors[..] {
+            [ErrorDetail::Parser(ref e)] => {
+                let context = e.context().expect("No context");
+                assert_eq!(context.production(), parser::Production::CallArguments);
+            }
+            ref other => panic!("Expected one parser error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn recovered_statement_error_reports_unbalanced_delimiter() {
+        let (_, errors) = parse_rust_file_with_recovery("fn a() { b(c]; d(); }");
+        match errors[..] {
+            [ErrorDetail::Parser(ref e)] => {
+                assert_eq!(e.delimiter(), Some(delimiters::DelimiterError::Mismatched {
+                    open: Extent(10, 11), close: Extent(12, 13), likely_open: None,
+                }));
+            }
+            ref other => panic!("Expected one parser error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn error_reports_unbalanced_delimiter() {
+        let e = parser_error("fn a() {}\nfn b( {}");
+        assert_eq!(e.delimiter(), Some(delimiters::DelimiterError::Unclosed {
+            open: Extent(14, 15), likely_close: None, likely_unclosed: None,
+        }));
+        assert!(e.to_string().ends_with("The delimiter at byte 14 is never closed"));
+    }
+
+    #[test]
+    fn error_with_text_reports_unbalanced_delimiter() {
+        let text = "fn a() {\n    b(c;\n}";
+        let e = parse_rust_file(text).unwrap_err();
+        assert!(e.with_text(text).to_string().ends_with(
+            "The `}` (line 3, column 1) does not close the `(` (line 2, column 6); \
+             it may have been intended to close the `{` (line 1, column 7)\n"
+        ));
+    }
+
+    #[test]
+    fn error_ignores_unbalanced_delimiter_in_other_items() {
+        let e = parser_error("fn a() { let x = ; }\nfn b() { ( }");
+        assert_eq!(e.delimiter(), None);
+    }
+
+    #[test]
+    fn fragment_error_reports_unbalanced_delimiter() {
+        match parse_expression("a(b").unwrap_err() {
+            ErrorDetail::Parser(e) => assert!(e.delimiter().is_some()),
+            e => panic!("Unexpected error: {}", e),
+        }
+    }
+
     #[test]
     fn error_on_last_token_does_not_panic() {
         let r = parse_rust_file("an_ident");
@@ -369,4 +945,209 @@ mod test {
         let r = parse_rust_file("c!(");
         assert!(r.is_err());
     }
+
+    #[test]
+    fn can_parse_an_expression() {
+        let e = parse_expression(" a + /* b */ b ").expect("Unable to parse");
+        assert_eq!(e.extent(), Extent(1, 14));
+        let b = e.value.as_binary().expect("Not a binary expression");
+        let ws = b.whitespace.iter().map(HasExtent::extent).collect::<Vec<_>>();
+        assert_eq!(ws, [Extent(2, 3), Extent(4, 5), Extent(5, 12), Extent(12, 13)]);
+    }
+
+    #[test]
+    fn error_on_expression_with_trailing_tokens() {
+        let e = parse_expression("a + b c").expect_err("Parsing should fail");
+        match e {
+            ErrorDetail::Parser(e) => {
+                assert_eq!(e.location, 6);
+                assert!(e.errors.contains(&parser::Error::ExpectedEndOfFile));
+            }
+            _ => panic!("Expected a parser error"),
+        }
+    }
+
+    #[test]
+    fn error_on_empty_expression() {
+        assert!(parse_expression("").is_err());
+    }
+
+    #[test]
+    fn error_on_expression_with_leading_shebang() {
+        assert!(parse_expression("#!x\n1").is_err());
+    }
+
+    #[test]
+    fn error_on_expression_with_leading_byte_order_mark() {
+        assert!(parse_expression("\u{feff}1").is_err());
+    }
+
+    #[test]
+    fn item_fragment_may_start_with_inner_attribute() {
+        let p = parse_item("#![allow(unused)]").expect("Unable to parse");
+        assert!(p.value.is_attribute_containing());
+    }
+
+    #[test]
+    fn can_parse_a_type() {
+        let t = parse_type("Vec<Option<u8>>").expect("Unable to parse");
+        assert_eq!(t.extent(), Extent(0, 15));
+    }
+
+    #[test]
+    fn can_parse_a_pattern() {
+        let p = parse_pattern("Some(a) | None").expect("Unable to parse");
+        assert!(p.kind.is_alternation());
+    }
+
+    #[test]
+    fn can_parse_an_item() {
+        let i = parse_item("#[derive(Debug)]\nstruct Point;").expect("Unable to parse");
+        assert_eq!(i.attributes.len(), 1);
+        assert!(i.value.is_struct());
+    }
+
+    #[test]
+    fn error_on_multiple_items() {
+        assert!(parse_item("struct A; struct B;").is_err());
+    }
+
+    #[test]
+    fn error_on_item_with_invalid_fragment_specifier() {
+        assert!(parse_item("macro_rules! a { ($b:exp) => {} }").is_err());
+    }
+
+    #[test]
+    fn can_parse_a_statement() {
+        let s = parse_statement("let a = 1;").expect("Unable to parse");
+        assert!(s.is_expression());
+
+        let s = parse_statement("let a = 1").expect("Unable to parse");
+        assert!(s.is_expression());
+    }
+
+    #[test]
+    fn can_parse_a_block() {
+        let b = parse_block("{ a(); b(); }").expect("Unable to parse");
+        assert_eq!(b.extent(), Extent(0, 13));
+        assert_eq!(b.statements.len(), 2);
+    }
+
+    #[test]
+    fn recovery_skips_malformed_items() {
+        let (file, errors) = parse_rust_file_with_recovery("struct A; struct { } fn b() {}");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(file.items.len(), 3);
+        assert!(file.items[0].value.is_struct());
+        assert_eq!(file.items[1].value.as_error(), Some(&Extent(10, 20)));
+        assert!(file.items[2].value.is_function());
+    }
+
+    #[test]
+    fn recovery_skips_balanced_delimiters() {
+        let (file, errors) = parse_rust_file_with_recovery("fn a( { ) ; } } fn b() {}");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(file.items.len(), 3);
+        assert_eq!(file.items[0].value.as_error(), Some(&Extent(0, 13)));
+        assert_eq!(file.items[1].value.as_error(), Some(&Extent(14, 15)));
+        assert!(file.items[2].value.is_function());
+    }
+
+    #[test]
+    fn recovery_skips_malformed_statements() {
+        let (file, errors) = parse_rust_file_with_recovery("fn a() { b(); let = { c }; d() }");
+        assert_eq!(errors.len(), 1);
+        match errors[0] {
+            ErrorDetail::Parser(ref e) => assert_eq!(e.location, 18),
+            ref other => panic!("Expected a parser error, got {:?}", other),
+        }
+
+        let f = file.items[0].value.as_function().expect("Not a function");
+        let stmts = &f.body.statements;
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[1].as_error(), Some(&Extent(14, 26)));
+        assert!(f.body.expression.is_some());
+    }
+
+    #[test]
+    fn recovery_skips_malformed_statements_in_nested_blocks() {
+        let (file, errors) = parse_rust_file_with_recovery("fn a() { if b { c d; } e(); }");
+        assert_eq!(errors.len(), 1);
+
+        let f = file.items[0].value.as_function().expect("Not a function");
+        assert_eq!(f.body.statements.len(), 2);
+    }
+
+    #[test]
+    fn recovery_skips_malformed_statements_at_end_of_block() {
+        let (file, errors) = parse_rust_file_with_recovery("fn a() { b(); ) }");
+        assert_eq!(errors.len(), 1);
+
+        let f = file.items[0].value.as_function().expect("Not a function");
+        assert_eq!(f.body.statements.len(), 2);
+        assert_eq!(f.body.statements[1].as_error(), Some(&Extent(14, 15)));
+    }
+
+    #[test]
+    fn recovery_assigns_whitespace_in_malformed_source() {
+        let (file, errors) = parse_rust_file_with_recovery("fn a() { /* x */ 1 2; }\nstruct /* y */ { }");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(file.items.len(), 2);
+    }
+
+    #[test]
+    fn recovery_reports_tokenizer_errors() {
+        let (file, errors) = parse_rust_file_with_recovery("fn a() { \"unterminated }");
+        assert_eq!(file.items.len(), 2);
+        assert_eq!(file.items[1].value.as_error(), Some(&Extent(9, 24)));
+        assert!(matches!(errors[..], [ErrorDetail::Parser(_), ErrorDetail::Tokenizer(_)]));
+    }
+
+    #[test]
+    fn recovery_keeps_items_before_tokenizer_errors() {
+        let text = "fn a() {}\nfn b() { '\\q' }\nfn c() {}";
+        let (file, errors) = parse_rust_file_with_recovery(text);
+        assert!(file.items[0].value.is_function());
+        let rest = file.items.last().and_then(|i| i.value.as_error()).expect("Must end with an error");
+        assert_eq!(&text[rest], "'\\q' }\nfn c() {}");
+        assert!(matches!(errors.last(), Some(ErrorDetail::Tokenizer(_))));
+    }
+
+    #[test]
+    fn recovery_reports_each_invalid_fragment_specifier() {
+        let (_, errors) = parse_rust_file_with_recovery(
+            "macro_rules! a { ($b:exp) => {} }\nfn c() { macro_rules! d { ($e:typ) => {} } f(); }"
+        );
+        assert_eq!(errors.len(), 2);
+        for e in errors {
+            match e {
+                ErrorDetail::Parser(e) => assert!(e.errors.contains(&parser::Error::InvalidFragmentSpecifier)),
+                other => panic!("Expected a parser error, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn file_inner_attributes_are_not_items() {
+        let text = "//! Docs\n#![allow(unused)]\nfn a() {}";
+        let file = parse_rust_file(text).expect("Unable to parse");
+        assert_eq!(file.attributes.len(), 2);
+        assert_eq!(&text[file.attributes[1].extent()], "#![allow(unused)]");
+        assert_eq!(file.items.len(), 1);
+        assert!(file.items[0].value.is_function());
+    }
+
+    #[test]
+    fn file_with_only_inner_attributes() {
+        let file = parse_rust_file("#![no_std]").expect("Unable to parse");
+        assert_eq!(file.attributes.len(), 1);
+        assert!(file.items.is_empty());
+    }
+
+    #[test]
+    fn valid_file_has_no_recovered_errors() {
+        let (file, errors) = parse_rust_file_with_recovery("fn a() { b(); c }");
+        assert!(errors.is_empty());
+        assert_eq!(file.items.len(), 1);
+    }
 }
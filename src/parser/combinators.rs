@@ -1,7 +1,10 @@
-use peresil;
+use peresil::{self, Recoverable};
+
+use std::mem;
 
 use crate::Extent;
-use super::{Master, Progress, Point, Error};
+use crate::ast::{Delimiter, Spacing, TokenTree, TokenTreeGroup, TokenTreeLeaf};
+use super::{Master, Progress, Point, Error, Context, Production};
 use crate::tokenizer::Token;
 
 use peresil::combinators::{map, IntoAppend};
@@ -16,6 +19,35 @@ pub(crate) fn ext<'s, F, T>(f: F) -> impl FnOnce(&mut Master<'s>, Point<'s>) ->
     }
 }
 
+/// Reports errors after the start of the parser as occurring within
+/// the production.
+pub(crate) fn context<'s, F, T>(production: Production, f: F) ->
+    impl FnOnce(&mut Master<'s>, Point<'s>) -> Progress<'s, T>
+    where F: FnOnce(&mut Master<'s>, Point<'s>) -> Progress<'s, T>
+{
+    named_context(production, None, f)
+}
+
+/// Like `context`, but the production belongs to a named item, such
+/// as the arguments of a specific function.
+pub(crate) fn named_context<'s, F, T, N>(production: Production, name: N, f: F) ->
+    impl FnOnce(&mut Master<'s>, Point<'s>) -> Progress<'s, T>
+    where F: FnOnce(&mut Master<'s>, Point<'s>) -> Progress<'s, T>,
+          N: Into<Option<Extent>>,
+{
+    let context = Context::new(production, name.into());
+
+    move |pm, pt| {
+        pm.state.contexts.push((context, pt.location()));
+        let progress = f(pm, pt);
+        if let peresil::Status::Failure(_) = progress.status {
+            pm.state.record_failure(progress.point);
+        }
+        pm.state.contexts.pop();
+        progress
+    }
+}
+
 pub(crate) fn parse_nested_until<'s, O, C>(is_open: O, is_close: C) ->
     impl Fn(&mut Master<'s>, Point<'s>) -> Progress<'s, Extent>
     where O: Fn(&Token) -> bool,
@@ -46,6 +78,133 @@ pub(crate) fn parse_nested_until<'s, O, C>(is_open: O, is_close: C) ->
     }
 }
 
+// Token trees up to, but not including, the closing delimiter of the
+// enclosing group. Every nested delimiter must be balanced.
+pub(crate) fn token_trees_until_close<'s>(pm: &mut Master<'s>, spt: Point<'s>) ->
+    Progress<'s, (Extent, Vec<TokenTree>)>
+{
+    let mut open: Vec<Delimiter> = Vec::new();
+    let mut len = 0;
+
+    for token in spt.s {
+        let unbalanced = if token.is_end_of_file() {
+            open.last().copied()
+        } else if let Some(delimiter) = opening_delimiter(token) {
+            open.push(delimiter);
+            None
+        } else if let Some(delimiter) = closing_delimiter(token) {
+            match open.last() {
+                None => break,
+                Some(&innermost) if innermost == delimiter => {
+                    open.pop();
+                    None
+                }
+                Some(&innermost) => Some(innermost),
+            }
+        } else {
+            None
+        };
+
+        if let Some(innermost) = unbalanced {
+            let pt = spt.advance_by(len);
+            pm.state.record_failure(pt);
+            return Progress::failure(pt, expected_closing_delimiter(innermost));
+        }
+        if token.is_end_of_file() {
+            break;
+        }
+
+        len += 1;
+    }
+
+    let pt = spt.advance_by(len);
+    Progress::success(pt, (pm.state.ex(spt, pt), token_trees(spt.s, len)))
+}
+
+// The tokens must be balanced. The token after the first `len`
+// tokens is only used to determine spacing.
+fn token_trees(tokens: &[Token], len: usize) -> Vec<TokenTree> {
+    let mut stack: Vec<(Extent, Delimiter, Vec<TokenTree>)> = Vec::new();
+    let mut trees = Vec::new();
+
+    for (i, token) in tokens[..len].iter().enumerate() {
+        let extent = token.extent();
+
+        if let Some(delimiter) = opening_delimiter(token) {
+            stack.push((extent, delimiter, mem::take(&mut trees)));
+        } else if closing_delimiter(token).is_some() {
+            let (open, delimiter, parent) = stack.pop().expect("Group must be open");
+            let children = mem::replace(&mut trees, parent);
+            trees.push(TokenTree::Group(TokenTreeGroup {
+                extent: Extent(open.0, extent.1),
+                delimiter,
+                trees: children,
+            }));
+        } else {
+            let spacing = spacing(token, tokens.get(i + 1));
+            trees.push(TokenTree::Leaf(TokenTreeLeaf { extent, token: *token, spacing }));
+        }
+    }
+
+    trees
+}
+
+// Like `proc_macro`, only punctuation immediately followed by more
+// punctuation is joint
+fn spacing(token: &Token, next: Option<&Token>) -> Spacing {
+    match next {
+        Some(next) if token.is_punctuation() && next.is_punctuation() &&
+            next.extent().0 == token.extent().1 => Spacing::Joint,
+        _ => Spacing::Alone,
+    }
+}
+
+fn expected_closing_delimiter(delimiter: Delimiter) -> Error {
+    match delimiter {
+        Delimiter::Paren => Error::ExpectedRightParen,
+        Delimiter::Square => Error::ExpectedRightSquare,
+        Delimiter::Curly => Error::ExpectedRightCurly,
+    }
+}
+
+pub(crate) fn token_tree_leaf<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, TokenTreeLeaf> {
+    let token = match pt.s.first() {
+        Some(token) if pt.sub_offset.is_none() => token,
+        _ => {
+            pm.state.record_failure(pt);
+            return Progress::failure(pt, Error::ExpectedToken);
+        }
+    };
+
+    if token.is_end_of_file() || opening_delimiter(token).is_some() || closing_delimiter(token).is_some() {
+        pm.state.record_failure(pt);
+        return Progress::failure(pt, Error::ExpectedToken);
+    }
+
+    let extent = token.extent();
+    let spacing = spacing(token, pt.s.get(1));
+
+    Progress::success(pt.advance_by(1), TokenTreeLeaf { extent, token: *token, spacing })
+}
+
+fn opening_delimiter(token: &Token) -> Option<Delimiter> {
+    match *token {
+        Token::LeftParen(_) => Some(Delimiter::Paren),
+        Token::LeftCurly(_) => Some(Delimiter::Curly),
+        Token::LeftSquare(_) => Some(Delimiter::Square),
+        _ => None,
+    }
+}
+
+fn closing_delimiter(token: &Token) -> Option<Delimiter> {
+    match *token {
+        Token::RightParen(_) => Some(Delimiter::Paren),
+        Token::RightCurly(_) => Some(Delimiter::Curly),
+        Token::RightSquare(_) => Some(Delimiter::Square),
+        _ => None,
+    }
+}
+
 pub(crate) enum TailedState<P, T, E> {
     Nothing(P, E),
     ValueOnly(P, T),
@@ -108,7 +267,10 @@ pub(crate) fn zero_or_more_tailed_append<'s, S, F, T, U>(append_to: Tailed<T>, s
         let mut tailed = append_to;
         loop {
             match parse_tailed(&sep, &f, pm, pt) {
-                TailedState::Nothing(pt, _) => {
+                TailedState::Nothing(pt, f) => {
+                    if !f.recoverable() {
+                        return Progress::failure(pt, f);
+                    }
                     return Progress::success(pt, tailed);
                 }
                 TailedState::ValueOnly(pt, v) => {
@@ -175,6 +337,9 @@ pub(crate) fn zero_or_more_tailed_values_resume<'s, S, F, T, U>(sep: S, f: F) ->
         };
 
         match one_or_more_tailed_values(sep, f)(pm, pt) {
+            Progress { status: peresil::Status::Failure(f), point } if !f.recoverable() => {
+                Progress::failure(point, f)
+            }
             Progress { status: peresil::Status::Failure(_), .. } => {
                 // We parsed the separator, but not another value. Rewind to before the separator
                 Progress::success(spt, Vec::new())
@@ -194,7 +359,10 @@ pub(crate) fn zero_or_more_implicitly_tailed_append<'s, S, F, T, U>(append_to: T
         let mut tailed = append_to;
         loop {
             match parse_tailed(&sep, &f, pm, pt) {
-                TailedState::Nothing(pt, _) => {
+                TailedState::Nothing(pt, f) => {
+                    if !f.recoverable() {
+                        return Progress::failure(pt, f);
+                    }
                     return Progress::success(pt, tailed);
                 }
                 TailedState::ValueOnly(pt2, v) => {
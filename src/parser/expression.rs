@@ -4,21 +4,48 @@
 /// messages are accurate. In addition to precedence, it is also needed
 /// to reduce the total depth of recursion.
 
-use peresil;
+use peresil::{self, Recoverable};
 use peresil::combinators::*;
 
 use std;
 use crate::Extent;
 use super::*;
 use crate::ast::*;
-use crate::tokenizer::Token;
 
 pub(crate) fn expression<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
     Progress<'s, Attributed<Expression>>
 {
-    match expression_shunting_yard(pm, pt, |_, state| state) {
+    let r = outside_let_chain(pm, |pm| {
+        expression_shunting_yard(pm, pt, LazyBoolean::Allowed, |_, state| state)
+    });
+
+    match r {
+        Ok(ShuntCar { value: expr, ept, .. }) => Progress::success(ept, expr),
+        Err((failure_point, err)) => {
+            pm.state.record_failure(failure_point);
+            Progress::failure(failure_point, err)
+        }
+    }
+}
+
+// The scrutinee of a `let` in a let-chain stops before any `&&` or
+// `||`, allowing the chain to continue with the next condition:
+//
+// if let Some(a) = b && let Ok(c) = a.parse() {}
+//                  ^                ^^^^^^^^^
+fn expression_let_scrutinee<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, Attributed<Expression>>
+{
+    let r = outside_let_chain(pm, |pm| {
+        expression_shunting_yard(pm, pt, LazyBoolean::Disallowed, |_, state| state)
+    });
+
+    match r {
         Ok(ShuntCar { value: expr, ept, .. }) => Progress::success(ept, expr),
-        Err((failure_point, err)) => Progress::failure(failure_point, err),
+        Err((failure_point, err)) => {
+            pm.state.record_failure(failure_point);
+            Progress::failure(failure_point, err)
+        }
     }
 }
 
@@ -27,7 +54,7 @@ pub(crate) fn expression<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
 pub(crate) fn statement_expression<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
     Progress<'s, Attributed<Expression>>
 {
-    let r = expression_shunting_yard(pm, pt, |shunting_yard, state| {
+    let r = outside_let_chain(pm, |pm| expression_shunting_yard(pm, pt, LazyBoolean::Allowed, |shunting_yard, state| {
         match state {
             ExpressionState::Atom => {
                 // If there are pending operators, they are prefix
@@ -49,11 +76,14 @@ pub(crate) fn statement_expression<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
             }
             _ => state
         }
-    });
+    }));
 
     match r {
         Ok(ShuntCar { value: expr, ept, .. }) => Progress::success(ept, expr),
-        Err((failure_point, err)) => Progress::failure(failure_point, err),
+        Err((failure_point, err)) => {
+            pm.state.record_failure(failure_point);
+            Progress::failure(failure_point, err)
+        }
     }
 }
 
@@ -66,7 +96,18 @@ enum ExpressionState {
     AtomLimitedPostfix,
 }
 
-fn expression_shunting_yard<'s, F>(pm: &mut Master<'s>, mut pt: Point<'s>, adapt_state: F) ->
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum LazyBoolean {
+    Allowed,
+    Disallowed,
+}
+
+fn expression_shunting_yard<'s, F>(
+    pm: &mut Master<'s>,
+    mut pt: Point<'s>,
+    lazy_boolean: LazyBoolean,
+    adapt_state: F,
+) ->
     ExprResult<'s, ShuntCar<'s, Attributed<Expression>>>
 where
     F: Fn(&ShuntingYard, ExpressionState) -> ExpressionState
@@ -106,7 +147,10 @@ where
                         }
                         pt = point;
                     }
-                    peresil::Progress { status: peresil::Status::Failure(_), point } => {
+                    peresil::Progress { status: peresil::Status::Failure(f), point } => {
+                        if !f.recoverable() {
+                            return Err((point, f));
+                        }
                         return shunting_yard.finish(pm, point);
                     }
                 }
@@ -116,6 +160,11 @@ where
                 match expression_infix_or_postfix(pm, pt) {
                     peresil::Progress { status: peresil::Status::Success(infix_or_postfix), point } => {
                         match infix_or_postfix {
+                            InfixOrPostfix::Infix(ref op) if
+                                lazy_boolean == LazyBoolean::Disallowed && op.is_lazy_boolean() =>
+                            {
+                                return shunting_yard.finish(pm, pt);
+                            }
                             InfixOrPostfix::Infix(op) => {
                                 let was_range = op.is_range();
                                 shunting_yard.add_infix(pm, op, pt, point)?;
@@ -128,7 +177,10 @@ where
                         }
                         pt = point;
                     }
-                    peresil::Progress { status: peresil::Status::Failure(_), point } => {
+                    peresil::Progress { status: peresil::Status::Failure(f), point } => {
+                        if !f.recoverable() {
+                            return Err((point, f));
+                        }
                         return shunting_yard.finish(pm, point);
                     }
                 }
@@ -140,7 +192,10 @@ where
                         state = ExpressionState::Postfix;
                         pt = point;
                     }
-                    peresil::Progress { status: peresil::Status::Failure(_), point } => {
+                    peresil::Progress { status: peresil::Status::Failure(f), point } => {
+                        if !f.recoverable() {
+                            return Err((point, f));
+                        }
                         return shunting_yard.finish(pm, point);
                     }
                 }
@@ -163,7 +218,7 @@ enum OperatorPrefix {
 }
 
 #[derive(Debug)]
-enum OperatorInfix {
+pub(crate) enum OperatorInfix {
     Add(Extent),
     AddAssign(Extent),
     Assign(Extent),
@@ -205,12 +260,18 @@ impl OperatorInfix {
             _ => false,
         }
     }
+
+    fn is_lazy_boolean(&self) -> bool {
+        use self::OperatorInfix::*;
+        matches!(*self, BooleanAnd(..) | BooleanOr(..))
+    }
 }
 
 #[derive(Debug)]
 enum OperatorPostfix {
     Ascription { typ: Type },
     AsType { typ: Type },
+    Await,
     Call { args: Vec<Attributed<Expression>> },
     FieldAccess { field: FieldName },
     Slice { index: Attributed<Expression> },
@@ -324,7 +385,7 @@ fn operator_prefix_reference<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
     }, |_, _| OperatorPrefix::Reference { is_mutable })
 }
 
-fn operator_infix<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+pub(crate) fn operator_infix<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
     Progress<'s, OperatorInfix>
 {
     pm.alternate(pt)
@@ -372,6 +433,7 @@ fn operator_postfix<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
         .one(operator_postfix_as_type)
         .one(operator_postfix_ascription)
         .one(operator_postfix_call)
+        .one(operator_postfix_await)
         .one(operator_postfix_field_access)
         .one(operator_postfix_slice)
         .one(map(question_mark, OperatorPostfix::Try))
@@ -382,6 +444,7 @@ fn operator_postfix_limited<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
     Progress<'s, OperatorPostfix>
 {
     pm.alternate(pt)
+        .one(operator_postfix_await)
         .one(operator_postfix_field_access)
         .one(map(question_mark, OperatorPostfix::Try))
         .finish()
@@ -411,11 +474,20 @@ fn operator_postfix_call<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
 {
     sequence!(pm, pt, {
         _    = left_paren;
-        args = head_expression_no_longer_ambiguous(zero_or_more_tailed_values(comma, expression));
+        args = context(Production::CallArguments, head_expression_no_longer_ambiguous(zero_or_more_tailed_values(comma, expression)));
         _    = right_paren;
     }, |_, _| OperatorPostfix::Call { args })
 }
 
+fn operator_postfix_await<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, OperatorPostfix>
+{
+    sequence!(pm, pt, {
+        _ = period;
+        _ = kw_await;
+    }, |_, _| OperatorPostfix::Await)
+}
+
 fn operator_postfix_field_access<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
     Progress<'s, OperatorPostfix>
 {
@@ -432,9 +504,9 @@ fn field_name<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, FieldName>
         .finish()
 }
 
+// Only accepting simple numbers allows splitting `0.1` in `x.0.1`
 fn field_name_number<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
-    number_normal(pm, pt)
-        .and_then(pt, |n| n.into_simple().ok_or(Error::ExpectedNumber))
+    token(|t| t.into_number().and_then(tokenizer::Number::into_simple), Error::ExpectedNumber)(pm, pt)
 }
 
 // TODO: avoid recursion here
@@ -451,13 +523,13 @@ fn operator_postfix_slice<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
 fn expression_atom<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Expression> {
     pm.alternate(pt)
         .one(map(expr_if, Expression::If))
-        .one(map(expr_if_let, Expression::IfLet))
         .one(map(expr_for_loop, Expression::ForLoop))
         .one(map(expr_loop, Expression::Loop))
         .one(map(expr_while, Expression::While))
-        .one(map(expr_while_let, Expression::WhileLet))
         .one(map(expr_match, Expression::Match))
         .one(map(expr_unsafe_block, Expression::UnsafeBlock))
+        .one(map(expr_const_block, Expression::ConstBlock))
+        .one(map(expr_labeled_block, Expression::LabeledBlock))
         .one(map(expr_async_block, Expression::AsyncBlock))
         .one(map(expr_block, Expression::Block))
         .one(map(expr_macro_call, Expression::MacroCall))
@@ -473,6 +545,7 @@ fn expression_atom<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Expre
         .one(map(number_literal, Expression::Number))
         .one(map(expr_byte, Expression::Byte))
         .one(map(expr_byte_string, Expression::ByteString))
+        .one(map(expr_c_string, Expression::CString))
         .one(map(expr_disambiguation, Expression::Disambiguation))
         .one(map(expr_value, Expression::Value))
         .finish()
@@ -731,6 +804,15 @@ impl<'s> ShuntingYard<'s> {
                     }).into()
                 })
             },
+            Postfix(OperatorPostfix::Await) => {
+                self.apply_postfix(pm, op_range, |extent, expr| {
+                    Expression::Await(Await {
+                        extent,
+                        target: Box::new(expr),
+                        whitespace: Vec::new(),
+                    })
+                })
+            },
             Postfix(OperatorPostfix::Try(..)) => {
                 self.apply_postfix(pm, op_range, |extent, expr| {
                     Expression::TryOperator(TryOperator {
@@ -876,39 +958,48 @@ pub(crate) fn expr_macro_call<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progres
         extent: pm.state.ex(spt, pt),
         name,
         arg,
-        args,
+        args: args.0,
+        tokens: args.1,
         whitespace: Vec::new(),
     })
 }
 
-fn expr_macro_call_args<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MacroCallArgs> {
+fn expr_macro_call_args<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (MacroCallArgs, Vec<TokenTree>)>
+{
     pm.alternate(pt)
-        .one(map(expr_macro_call_paren, MacroCallArgs::Paren))
-        .one(map(expr_macro_call_square, MacroCallArgs::Square))
-        .one(map(expr_macro_call_curly, MacroCallArgs::Curly))
+        .one(map(expr_macro_call_paren, |(e, t)| (MacroCallArgs::Paren(e), t)))
+        .one(map(expr_macro_call_square, |(e, t)| (MacroCallArgs::Square(e), t)))
+        .one(map(expr_macro_call_curly, |(e, t)| (MacroCallArgs::Curly(e), t)))
         .finish()
 }
 
-fn expr_macro_call_paren<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
+fn expr_macro_call_paren<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (Extent, Vec<TokenTree>)>
+{
     sequence!(pm, pt, {
         _    = left_paren;
-        args = parse_nested_until(Token::is_left_paren, Token::is_right_paren);
+        args = token_trees_until_close;
         _    = right_paren;
     }, |_, _| args)
 }
 
-fn expr_macro_call_square<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
+fn expr_macro_call_square<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (Extent, Vec<TokenTree>)>
+{
     sequence!(pm, pt, {
         _    = left_square;
-        args = parse_nested_until(Token::is_left_square, Token::is_right_square);
+        args = token_trees_until_close;
         _    = right_square;
     }, |_, _| args)
 }
 
-fn expr_macro_call_curly<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
+fn expr_macro_call_curly<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (Extent, Vec<TokenTree>)>
+{
     sequence!(pm, pt, {
         _    = left_curly;
-        args = parse_nested_until(Token::is_left_curly, Token::is_right_curly);
+        args = token_trees_until_close;
         _    = right_curly;
     }, |_, _| args)
 }
@@ -917,18 +1008,35 @@ fn expr_let<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Let> {
     sequence!(pm, pt, {
         spt     = point;
         _       = kw_let;
-        pattern = pattern;
+        pattern = expr_let_pattern;
         typ     = optional(expr_let_type);
         value   = optional(expr_let_rhs);
-    }, |pm: &mut Master, pt| Let {
-        extent: pm.state.ex(spt, pt),
-        pattern,
-        typ,
-        value: value.map(Box::new),
-        whitespace: Vec::new(),
+    }, |pm: &mut Master, pt| {
+        let (value, else_block) = match value {
+            Some((value, else_block)) => (Some(value), else_block),
+            None => (None, None),
+        };
+        Let {
+            extent: pm.state.ex(spt, pt),
+            pattern,
+            typ,
+            value: value.map(Box::new),
+            else_block: else_block.map(Box::new),
+            whitespace: Vec::new(),
+        }
     })
 }
 
+// Only a `let` in a condition may have a top-level alternation;
+// `let A | B = x;` needs parentheses around the pattern.
+fn expr_let_pattern<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Pattern> {
+    if pm.state.in_let_chain {
+        pattern(pm, pt)
+    } else {
+        pattern_single(pm, pt)
+    }
+}
+
 fn expr_let_type<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Type> {
     sequence!(pm, pt, {
         _   = colon;
@@ -936,18 +1044,51 @@ fn expr_let_type<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Type> {
     }, |_, _| typ)
 }
 
-fn expr_let_rhs<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Attributed<Expression>> {
-    sequence!(pm, pt, {
-        _     = equals;
-        value = expression;
-    }, |_, _| value)
+fn expr_let_rhs<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (Attributed<Expression>, Option<Block>)>
+{
+    let (pt, _) = try_parse!(equals(pm, pt));
+
+    // We are directly in the condition of an `if` or `while`, so
+    // this `let` may be part of a let-chain
+    if pm.state.in_let_chain {
+        return expression_let_scrutinee(pm, pt).map(|value| (value, None));
+    }
+
+    let (pt, value) = try_parse!(expression(pm, pt));
+
+    let else_pt = pt;
+    let pt = match kw_else(pm, pt) {
+        peresil::Progress { status: peresil::Status::Success(_), point } => point,
+        peresil::Progress { status: peresil::Status::Failure(_), .. } => {
+            return Progress::success(pt, (value, None));
+        }
+    };
+
+    // The initializer of a `let ... else` may not end with a closing
+    // brace or be a lazy boolean, otherwise `else` would be ambiguous.
+    if !is_valid_let_else_initializer(&value) {
+        pm.state.record_fatal_failure(else_pt);
+        return Progress::failure(else_pt, Error::InvalidLetElseInitializer);
+    }
+
+    let (pt, else_block) = try_parse!(block(pm, pt));
+    Progress::success(pt, (value, Some(else_block)))
+}
+
+fn is_valid_let_else_initializer(value: &Expression) -> bool {
+    match *value {
+        Expression::Binary(Binary { op: BinaryOp::BooleanAnd, .. }) |
+        Expression::Binary(Binary { op: BinaryOp::BooleanOr, .. }) => false,
+        ref value => !value.ends_with_curly_brace(),
+    }
 }
 
 fn expr_if<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, If> {
     sequence!(pm, pt, {
         spt               = point;
         _                 = kw_if;
-        (condition, body) = expr_followed_by_block;
+        (condition, body) = expr_condition_followed_by_block;
         more              = zero_or_more(expr_if_else_if);
         else_body         = optional(expr_if_else_end);
     }, move |pm: &mut Master, pt| If {
@@ -981,6 +1122,72 @@ fn expr_followed_by_block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s
     }, |_, _| (condition, body))
 }
 
+// The condition of an `if` or `while`, where `let` may be chained
+// with `&&`
+fn expr_condition_followed_by_block<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (Attributed<Expression>, Block)>
+{
+    let condition_pt = pt;
+    let (pt, condition) = try_parse!(control_flow_head_expression(expr_condition)(pm, pt));
+
+    if !is_valid_condition(&condition.value) {
+        pm.state.record_fatal_failure(condition_pt);
+        return Progress::failure(condition_pt, Error::InvalidLetCondition);
+    }
+
+    let (pt, body) = try_parse!(block(pm, pt));
+    Progress::success(pt, (condition, body))
+}
+
+fn expr_condition<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Attributed<Expression>> {
+    let old = std::mem::replace(&mut pm.state.in_let_chain, true);
+    let r = expression_shunting_yard(pm, pt, LazyBoolean::Allowed, |_, state| state);
+    pm.state.in_let_chain = old;
+
+    match r {
+        Ok(ShuntCar { value: expr, ept, .. }) => Progress::success(ept, expr),
+        Err((failure_point, err)) => {
+            pm.state.record_failure(failure_point);
+            Progress::failure(failure_point, err)
+        }
+    }
+}
+
+// Any expression nested inside of the condition, such as a statement
+// in a block, a closure body, or a parenthesized expression, is no
+// longer part of the let-chain.
+fn outside_let_chain<'s, F, T>(pm: &mut Master<'s>, f: F) -> T
+    where F: FnOnce(&mut Master<'s>) -> T
+{
+    let old = std::mem::replace(&mut pm.state.in_let_chain, false);
+    let r = f(pm);
+    pm.state.in_let_chain = old;
+    r
+}
+
+// A `let` may only be chained to the rest of the condition with `&&`;
+// `if let a = b || c {}` is not `(let a = b) || c`.
+fn is_valid_condition(condition: &Expression) -> bool {
+    match *condition {
+        Expression::Let(_) => true,
+        Expression::Binary(Binary { op: BinaryOp::BooleanAnd, ref lhs, ref rhs, .. }) => {
+            is_valid_condition(&lhs.value) && is_valid_condition(&rhs.value)
+        }
+        ref other => !has_chained_let(other),
+    }
+}
+
+fn has_chained_let(expression: &Expression) -> bool {
+    match *expression {
+        Expression::Let(_) => true,
+        Expression::Binary(Binary { op: BinaryOp::BooleanAnd, ref lhs, ref rhs, .. }) |
+        Expression::Binary(Binary { op: BinaryOp::BooleanOr, ref lhs, ref rhs, .. }) => {
+            has_chained_let(&lhs.value) || has_chained_let(&rhs.value)
+        }
+        _ => false,
+    }
+}
+
 fn expr_for_loop<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ForLoop> {
     sequence!(pm, pt, {
         spt          = point;
@@ -1015,29 +1222,12 @@ fn expr_loop<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Loop> {
     }, |pm: &mut Master, pt| Loop { extent: pm.state.ex(spt, pt), label, body: Box::new(body), whitespace: Vec::new() })
 }
 
-fn expr_if_let<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, IfLet> {
-    sequence!(pm, pt, {
-        spt           = point;
-        _             = kw_if;
-        _             = kw_let;
-        pattern       = pattern;
-        _             = equals;
-        (value, body) = expr_followed_by_block;
-    }, |pm: &mut Master, pt| IfLet {
-        extent: pm.state.ex(spt, pt),
-        pattern,
-        value: Box::new(value),
-        body: Box::new(body),
-        whitespace: Vec::new(),
-    })
-}
-
 fn expr_while<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, While> {
     sequence!(pm, pt, {
         spt           = point;
         label         = optional(loop_label);
         _             = kw_while;
-        (value, body) = expr_followed_by_block;
+        (value, body) = expr_condition_followed_by_block;
     }, |pm: &mut Master, pt| While {
         extent: pm.state.ex(spt, pt),
         label,
@@ -1047,25 +1237,6 @@ fn expr_while<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, While> {
     })
 }
 
-fn expr_while_let<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, WhileLet> {
-    sequence!(pm, pt, {
-        spt           = point;
-        label         = optional(loop_label);
-        _             = kw_while;
-        _             = kw_let;
-        pattern       = pattern;
-        _             = equals;
-        (value, body) = expr_followed_by_block;
-    }, |pm: &mut Master, pt| WhileLet {
-        extent: pm.state.ex(spt, pt),
-        label,
-        pattern,
-        value: Box::new(value),
-        body: Box::new(body),
-        whitespace: Vec::new(),
-    })
-}
-
 impl ImplicitSeparator for MatchArm {
     fn is_implicit_separator(&self) -> bool {
         match self.hand {
@@ -1081,7 +1252,7 @@ fn expr_match<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Match> {
         _    = kw_match;
         head = control_flow_head_expression(expression);
         _    = left_curly;
-        arms = zero_or_more_implicitly_tailed_values(comma, match_arm);
+        arms = context(Production::MatchArms, zero_or_more_implicitly_tailed_values(comma, match_arm));
         _    = right_curly;
     }, |pm: &mut Master, pt| Match { extent: pm.state.ex(spt, pt), head: Box::new(head), arms, whitespace: Vec::new() })
 }
@@ -1090,7 +1261,7 @@ fn match_arm<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MatchArm> {
     sequence!(pm, pt, {
         spt        = point;
         attributes = zero_or_more(attribute);
-        pattern    = one_or_more_tailed_values(pipe, pattern);
+        pattern    = pattern;
         guard      = optional(match_arm_guard);
         _          = thick_arrow;
         hand       = match_arm_hand;
@@ -1188,13 +1359,24 @@ pub(crate) fn expr_byte_string<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progre
         .finish()
 }
 
+pub(crate) fn expr_c_string<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, CString> {
+    pm.alternate(pt)
+        .one(map(c_string, |extent| {
+            CString { extent, value: String { extent, value: extent } }  // FIXME: value
+        }))
+        .one(map(c_string_raw, |extent| {
+            CString { extent, value: String { extent, value: extent } }  // FIXME: value
+        }))
+        .finish()
+}
+
 fn expr_closure<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Closure> {
     sequence!(pm, pt, {
         spt                 = point;
         is_async            = optional(kw_async);
         is_move             = optional(kw_move);
         _                   = pipe;
-        args                = zero_or_more_tailed_values(comma, expr_closure_arg);
+        args                = context(Production::ClosureArguments, zero_or_more_tailed_values(comma, expr_closure_arg));
         _                   = pipe;
         (return_type, body) = expr_closure_return;
     }, |pm: &mut Master, pt| Closure {
@@ -1211,7 +1393,7 @@ fn expr_closure<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Closure>
 fn expr_closure_arg<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ClosureArg> {
     sequence!(pm, pt, {
         spt  = point;
-        name = pattern;
+        name = pattern_single;
         typ  = optional(expr_closure_arg_type);
     }, |pm: &mut Master, pt| ClosureArg {
         extent: pm.state.ex(spt, pt),
@@ -1298,6 +1480,7 @@ fn expr_break<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Break> {
 
 fn expr_block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Box<Block>> {
     if pm.state.expression_ambiguity == ExpressionAmbiguity::Maximum {
+        pm.state.record_failure(pt);
         Progress::failure(pt, Error::BlockNotAllowedHere)
     } else {
         block(pm, pt).map(Box::new)
@@ -1312,6 +1495,27 @@ fn expr_unsafe_block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Uns
     }, |pm: &mut Master, pt| UnsafeBlock { extent: pm.state.ex(spt, pt), body: Box::new(body), whitespace: Vec::new() })
 }
 
+pub(crate) fn expr_const_block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ConstBlock> {
+    sequence!(pm, pt, {
+        spt  = point;
+        _    = kw_const;
+        body = block;
+    }, |pm: &mut Master, pt| ConstBlock { extent: pm.state.ex(spt, pt), body: Box::new(body), whitespace: Vec::new() })
+}
+
+fn expr_labeled_block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, LabeledBlock> {
+    sequence!(pm, pt, {
+        spt   = point;
+        label = loop_label;
+        body  = block;
+    }, |pm: &mut Master, pt| LabeledBlock {
+        extent: pm.state.ex(spt, pt),
+        label,
+        body: Box::new(body),
+        whitespace: Vec::new(),
+    })
+}
+
 fn expr_async_block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, AsyncBlock> {
     sequence!(pm, pt, {
         spt     = point;
@@ -1401,7 +1605,7 @@ fn expr_value_struct_literal_splat<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
     }, |_, _| value)
 }
 
-fn expr_disambiguation<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Disambiguation> {
+pub(crate) fn expr_disambiguation<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Disambiguation> {
     sequence!(pm, pt, {
         spt        = point;
         core       = disambiguation_core;
@@ -1415,6 +1619,40 @@ fn expr_disambiguation<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, D
     })
 }
 
+// Only blocks and literals are allowed as const arguments, otherwise
+// we could not tell them apart from types.
+pub(crate) fn generic_argument_const<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Expression> {
+    pm.alternate(pt)
+        .one(map(head_expression_no_longer_ambiguous(block), |b| Expression::Block(Box::new(b))))
+        .one(expr_literal)
+        .one(generic_argument_const_negative)
+        .finish()
+}
+
+pub(crate) fn expr_literal<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Expression> {
+    pm.alternate(pt)
+        .one(map(number_literal, Expression::Number))
+        .one(map(character_literal, Expression::Character))
+        .one(map(string_literal, Expression::String))
+        .one(map(expr_byte, Expression::Byte))
+        .one(map(expr_byte_string, Expression::ByteString))
+        .one(map(expr_c_string, Expression::CString))
+        .finish()
+}
+
+fn generic_argument_const_negative<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Expression> {
+    sequence!(pm, pt, {
+        spt   = point;
+        _     = minus;
+        value = number_literal;
+    }, |pm: &mut Master, pt| Expression::Unary(Unary {
+        extent: pm.state.ex(spt, pt),
+        op: UnaryOp::Negate,
+        value: Box::new(Expression::Number(value).into()),
+        whitespace: Vec::new(),
+    }))
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ExpressionAmbiguity {
     Unambiguous,
@@ -1566,6 +1804,70 @@ mod test {
         assert_extent!(p, (0, 28));
     }
 
+    #[test]
+    fn expr_let_cannot_have_leading_pipe() {
+        let (err_loc, _) = unwrap_progress_err(parse_full(expression, "let | A = x"));
+        assert_eq!(err_loc, 1);
+    }
+
+    #[test]
+    fn expr_if_let_with_leading_pipe() {
+        let e = qp(expression, "if let | A | B = x {}");
+        let i = unwrap_as!(e.value, Expression::If);
+        let l = unwrap_as!(i.condition.value, Expression::Let);
+        assert_extent!(l.pattern, (7, 14));
+    }
+
+    #[test]
+    fn expr_let_with_parenthesized_alternation() {
+        let p = qp(expression, "let (Ok(x) | Err(x)) = r");
+        assert!(p.is_let());
+        assert_extent!(p, (0, 24));
+    }
+
+    #[test]
+    fn expr_let_else() {
+        let e = qp(expression, "let Some(x) = opt else { return }");
+
+        let l = unwrap_as!(e.value, Expression::Let);
+        assert_extent!(l, (0, 33));
+        assert_extent!(l.value.expect("Must have a value"), (14, 17));
+        assert_extent!(l.else_block.expect("Must have an else block"), (23, 33));
+    }
+
+    #[test]
+    fn expr_let_else_with_type() {
+        let p = qp(expression, "let Ok(x): Result<u8, ()> = r else { panic!() }");
+        assert!(p.is_let());
+        assert_extent!(p, (0, 47));
+    }
+
+    #[test]
+    fn expr_let_else_with_parenthesized_initializer() {
+        let p = qp(expression, "let true = (a && b) else { return }");
+        assert_extent!(p, (0, 35));
+    }
+
+    #[test]
+    fn expr_let_else_initializer_cannot_be_lazy_boolean() {
+        let (err_loc, errs) = unwrap_progress_err(parse_full(block, "{ let true = a && b else { return }; }"));
+        assert_eq!(err_loc, 7);
+        assert!(errs.contains(&Error::InvalidLetElseInitializer));
+    }
+
+    #[test]
+    fn expr_let_else_initializer_cannot_end_with_curly_brace() {
+        let (err_loc, errs) = unwrap_progress_err(parse_full(block, "{ let Some(x) = S { a } else { return }; }"));
+        assert_eq!(err_loc, 11);
+        assert!(errs.contains(&Error::InvalidLetElseInitializer));
+    }
+
+    #[test]
+    fn expr_let_else_initializer_cannot_end_with_nested_curly_brace() {
+        let (_, errs) = unwrap_progress_err(parse_full(block, "{ let x = 1 + match y {} else { return }; }"));
+        assert!(errs.contains(&Error::InvalidLetElseInitializer));
+    }
+
     #[test]
     fn expr_let_mut() {
         let p = qp(expression, "let mut pm = Master::new()");
@@ -1638,6 +1940,102 @@ mod test {
         assert_extent!(v3, (0, 3));
     }
 
+    #[test]
+    fn expr_field_access_nested_numbers() {
+        let e = qp(expression, "foo.0.1");
+
+        let fa1 = unwrap_as!(e.value, Expression::FieldAccess);
+        assert_extent!(fa1, (0, 7));
+        assert_extent!(fa1.field, (6, 7));
+
+        let fa2 = unwrap_as!(fa1.target.value, Expression::FieldAccess);
+        assert_extent!(fa2, (0, 5));
+        assert_extent!(fa2.field, (4, 5));
+    }
+
+    #[test]
+    fn expr_field_access_nested_multi_digit_numbers() {
+        let e = qp(expression, "foo.12.345.6");
+
+        let fa1 = unwrap_as!(e.value, Expression::FieldAccess);
+        assert_extent!(fa1, (0, 12));
+        assert_extent!(fa1.field, (11, 12));
+
+        let fa2 = unwrap_as!(fa1.target.value, Expression::FieldAccess);
+        assert_extent!(fa2, (0, 10));
+        assert_extent!(fa2.field, (7, 10));
+
+        let fa3 = unwrap_as!(fa2.target.value, Expression::FieldAccess);
+        assert_extent!(fa3, (0, 6));
+        assert_extent!(fa3.field, (4, 6));
+    }
+
+    #[test]
+    fn expr_field_access_nested_numbers_followed_by_postfix() {
+        let e = qp(expression, "foo.0.1.bar()?");
+        assert_extent!(e, (0, 14));
+
+        let t = unwrap_as!(e.value, Expression::TryOperator);
+        let c = unwrap_as!(t.target.value, Expression::Call);
+        let fa1 = unwrap_as!(c.target.value, Expression::FieldAccess);
+        assert_extent!(fa1.field, (8, 11));
+
+        let fa2 = unwrap_as!(fa1.target.value, Expression::FieldAccess);
+        assert_extent!(fa2, (0, 7));
+        assert_extent!(fa2.field, (6, 7));
+    }
+
+    #[test]
+    fn expr_field_access_nested_numbers_with_whitespace() {
+        let e = qp(expression, "foo . 0.1 .2");
+
+        let fa1 = unwrap_as!(e.value, Expression::FieldAccess);
+        assert_extent!(fa1, (0, 12));
+        assert_extent!(fa1.field, (11, 12));
+
+        let fa2 = unwrap_as!(fa1.target.value, Expression::FieldAccess);
+        assert_extent!(fa2, (0, 9));
+        assert_extent!(fa2.field, (8, 9));
+
+        let fa3 = unwrap_as!(fa2.target.value, Expression::FieldAccess);
+        assert_extent!(fa3, (0, 7));
+        assert_extent!(fa3.field, (6, 7));
+    }
+
+    #[test]
+    fn expr_field_access_number_with_trailing_period() {
+        let e = qp(expression, "foo.0. 1");
+
+        let fa1 = unwrap_as!(e.value, Expression::FieldAccess);
+        assert_extent!(fa1, (0, 8));
+        assert_extent!(fa1.field, (7, 8));
+
+        let fa2 = unwrap_as!(fa1.target.value, Expression::FieldAccess);
+        assert_extent!(fa2, (0, 5));
+    }
+
+    #[test]
+    fn expr_field_access_nested_numbers_as_arguments() {
+        let e = qp(expression, "f(a.0.1, b.1.0)");
+        let c = unwrap_as!(e.value, Expression::Call);
+        assert_extent!(c.args[0], (2, 7));
+        assert_extent!(c.args[1], (9, 14));
+    }
+
+    #[test]
+    fn expr_field_access_nested_numbers_with_suffix_is_not_split() {
+        let (offset, e) = parse_full(expression, "foo.0.1u8").expect("Must parse the target");
+        assert_eq!(offset, 1);
+        assert_extent!(e, (0, 3));
+    }
+
+    #[test]
+    fn expr_field_access_nested_numbers_with_exponent_is_not_split() {
+        let (offset, e) = parse_full(expression, "foo.0.1e3").expect("Must parse the target");
+        assert_eq!(offset, 1);
+        assert_extent!(e, (0, 3));
+    }
+
     #[test]
     fn expr_call_function() {
         let e = qp(expression, "foo(a)");
@@ -1694,6 +2092,61 @@ mod test {
         assert_extent!(v5, (0, 3));
     }
 
+    #[test]
+    fn expr_await() {
+        let e = qp(expression, "foo.await");
+
+        let a1 = unwrap_as!(e.value, Expression::Await);
+        assert_extent!(a1, (0, 9));
+
+        let v2 = unwrap_as!(a1.target.value, Expression::Value);
+        assert_extent!(v2, (0, 3));
+    }
+
+    #[test]
+    fn expr_await_followed_by_try() {
+        let e = qp(expression, "foo.await?");
+
+        let t1 = unwrap_as!(e.value, Expression::TryOperator);
+        assert_extent!(t1, (0, 10));
+
+        let a2 = unwrap_as!(t1.target.value, Expression::Await);
+        assert_extent!(a2, (0, 9));
+    }
+
+    #[test]
+    fn expr_await_of_method_call() {
+        let e = qp(expression, "foo.bar(a).await");
+
+        let a1 = unwrap_as!(e.value, Expression::Await);
+        assert_extent!(a1, (0, 16));
+
+        let c2 = unwrap_as!(a1.target.value, Expression::Call);
+        assert_extent!(c2, (0, 10));
+    }
+
+    #[test]
+    fn expr_await_followed_by_method_call() {
+        let e = qp(expression, "foo.await.bar()");
+
+        let c1 = unwrap_as!(e.value, Expression::Call);
+        assert_extent!(c1, (0, 15));
+
+        let fa2 = unwrap_as!(c1.target.value, Expression::FieldAccess);
+        assert_extent!(fa2, (0, 13));
+        assert_extent!(fa2.field, (10, 13));
+
+        let a3 = unwrap_as!(fa2.target.value, Expression::Await);
+        assert_extent!(a3, (0, 9));
+    }
+
+    #[test]
+    fn expr_await_with_whitespace() {
+        let p = qp(expression, "foo . await");
+        assert!(p.is_await());
+        assert_extent!(p, (0, 11))
+    }
+
     #[test]
     fn expr_call_method_with_turbofish() {
         let p = qp(expression, "foo.bar::<u8>()");
@@ -1701,6 +2154,13 @@ mod test {
         assert_extent!(p, (0, 15))
     }
 
+    #[test]
+    fn expr_call_with_const_turbofish() {
+        let p = qp(expression, "Buf::<{ N + 1 }>::new()");
+        assert!(p.is_call());
+        assert_extent!(p, (0, 23))
+    }
+
     #[test]
     fn expr_call_method_with_turbofish_nested() {
         let p = qp(expression, "e.into_iter().collect::<BTreeSet<_>>()");
@@ -1728,6 +2188,12 @@ mod test {
         assert_extent!(p, (0, 18))
     }
 
+    #[test]
+    fn expr_for_loop_with_alternation() {
+        let p = qp(expression, "for A(a) | B(a) in c {}");
+        assert_extent!(p, (0, 23))
+    }
+
     #[test]
     fn expr_for_loop_with_label() {
         let p = qp(expression, "'a: for (a, b) in c {}");
@@ -1819,6 +2285,14 @@ mod test {
         assert_extent!(p, (0, 9))
     }
 
+    #[test]
+    fn expr_const_block() {
+        let p = qp(expression, "const { size_of::<T>() }");
+        assert_extent!(p, (0, 24));
+        let cb = unwrap_as!(p.value, Expression::ConstBlock);
+        assert_extent!(cb.body.expression.unwrap(), (8, 22))
+    }
+
     #[test]
     fn expr_async_block() {
         let p = qp(expression, "async {}");
@@ -1859,6 +2333,111 @@ mod test {
         assert_extent!(p, (0, 24))
     }
 
+    #[test]
+    fn expr_if_let_with_alternation() {
+        let p = qp(expression, "if let A | B = x {}");
+        assert_extent!(p, (0, 19))
+    }
+
+    #[test]
+    fn expr_if_let_chain() {
+        let e = qp(expression, "if let Some(a) = x && let Ok(b) = a {}");
+        let i = unwrap_as!(e.value, Expression::If);
+        assert_extent!(i, (0, 38));
+
+        let b = unwrap_as!(i.condition.value, Expression::Binary);
+        assert_eq!(b.op, BinaryOp::BooleanAnd);
+
+        let l1 = unwrap_as!(b.lhs.value, Expression::Let);
+        assert_extent!(l1, (3, 18));
+        assert_extent!(l1.value.expect("Must have a value"), (17, 18));
+
+        let l2 = unwrap_as!(b.rhs.value, Expression::Let);
+        assert_extent!(l2, (22, 35));
+    }
+
+    #[test]
+    fn expr_if_let_chain_with_boolean_conditions() {
+        let e = qp(expression, "if a && let Some(b) = c && d {}");
+        let i = unwrap_as!(e.value, Expression::If);
+
+        let b1 = unwrap_as!(i.condition.value, Expression::Binary);
+        assert_eq!(b1.op, BinaryOp::BooleanAnd);
+        assert_extent!(b1.rhs, (27, 28));
+
+        let b2 = unwrap_as!(b1.lhs.value, Expression::Binary);
+        let l = unwrap_as!(b2.rhs.value, Expression::Let);
+        assert_extent!(l, (8, 23));
+    }
+
+    #[test]
+    fn expr_if_let_chain_with_comparison() {
+        let p = qp(expression, "if let Some(a) = x && let Ok(b) = a.parse() && b > 3 { }");
+        assert!(p.is_if());
+        assert_extent!(p, (0, 56))
+    }
+
+    #[test]
+    fn expr_if_let_chain_cannot_use_lazy_or() {
+        let (err_loc, errs) = unwrap_progress_err(parse_full(expression, "if let a = b || c {}"));
+        assert_eq!(err_loc, 1);
+        assert!(errs.contains(&Error::InvalidLetCondition));
+    }
+
+    #[test]
+    fn expr_if_let_chain_cannot_follow_lazy_or() {
+        let (_, errs) = unwrap_progress_err(parse_full(expression, "if a || let b = c {}"));
+        assert!(errs.contains(&Error::InvalidLetCondition));
+    }
+
+    #[test]
+    fn expr_if_let_chain_allows_lazy_or_in_parentheses() {
+        let p = qp(expression, "if let a = b && (c || d) {}");
+        assert_extent!(p, (0, 27))
+    }
+
+    #[test]
+    fn expr_let_in_block_in_condition_includes_lazy_boolean() {
+        let e = qp(expression, "if { let x = a && b; x } {}");
+        let i = unwrap_as!(e.value, Expression::If);
+        let b = unwrap_as!(i.condition.value, Expression::Block);
+        let s = b.statements.into_iter().next().and_then(Statement::into_expression).expect("Must be an expression");
+        let l = unwrap_as!(s.value, Expression::Let);
+        assert_extent!(l, (5, 19));
+        assert!(l.value.expect("Must have a value").is_binary());
+    }
+
+    #[test]
+    fn expr_let_else_in_block_in_condition() {
+        let e = qp(expression, "if { let Some(x) = y else { return }; x } {}");
+        let i = unwrap_as!(e.value, Expression::If);
+        let b = unwrap_as!(i.condition.value, Expression::Block);
+        let s = b.statements.into_iter().next().and_then(Statement::into_expression).expect("Must be an expression");
+        let l = unwrap_as!(s.value, Expression::Let);
+        assert!(l.else_block.is_some());
+    }
+
+    #[test]
+    fn expr_let_in_closure_in_condition_includes_lazy_boolean() {
+        let e = qp(expression, "if (|| { let x = a || b; x })() {}");
+        assert_extent!(e, (0, 34));
+    }
+
+    #[test]
+    fn expr_let_outside_of_condition_includes_lazy_boolean() {
+        let e = qp(expression, "let a = b && c");
+        let l = unwrap_as!(e.value, Expression::Let);
+        assert!(l.value.expect("Must have a value").is_binary());
+    }
+
+    #[test]
+    fn expr_let_in_parenthesized_condition_includes_lazy_boolean() {
+        let e = qp(expression, "if (let a = b && c) {}");
+        let i = unwrap_as!(e.value, Expression::If);
+        let p = unwrap_as!(i.condition.value, Expression::Parenthetical);
+        assert!(p.expression.is_let());
+    }
+
     #[test]
     fn expr_while() {
         let p = qp(expression, "while is_awesome() {}");
@@ -1883,6 +2462,23 @@ mod test {
         assert_extent!(p, (0, 31))
     }
 
+    #[test]
+    fn expr_while_let_with_alternation() {
+        let p = qp(expression, "while let Some(A | B) = x {}");
+        assert_extent!(p, (0, 28))
+    }
+
+    #[test]
+    fn expr_while_let_chain() {
+        let e = qp(expression, "while let Some(a) = x.next() && a {}");
+        let w = unwrap_as!(e.value, Expression::While);
+        assert_extent!(w, (0, 36));
+
+        let b = unwrap_as!(w.value.value, Expression::Binary);
+        assert_eq!(b.op, BinaryOp::BooleanAnd);
+        assert_extent!(b.lhs, (6, 28));
+    }
+
     #[test]
     fn expr_binary_op() {
         let p = qp(expression, "a < b");
@@ -1973,6 +2569,57 @@ mod test {
         assert_extent!(p, (0, 8))
     }
 
+    #[test]
+    fn expr_macro_call_token_trees() {
+        let p = qp(expression, "vec![(1, 2), a::b]");
+        let m = unwrap_as!(p.value, Expression::MacroCall);
+        assert_extent!(m.args, (5, 17));
+        assert_eq!(m.tokens.len(), 5);
+
+        let g = m.tokens[0].as_group().expect("Must be a group");
+        assert_extent!(g, (5, 11));
+        assert_eq!(g.delimiter, Delimiter::Paren);
+        assert_eq!(g.trees.len(), 3);
+
+        let one = g.trees[0].as_leaf().expect("Must be a leaf");
+        assert_extent!(one, (6, 7));
+        assert_eq!(one.spacing, Spacing::Alone);
+
+        let comma = g.trees[1].as_leaf().expect("Must be a leaf");
+        assert_eq!(comma.spacing, Spacing::Alone);
+
+        let path = m.tokens[3].as_leaf().expect("Must be a leaf");
+        assert!(path.token.is_double_colon());
+
+        let b = m.tokens[4].as_leaf().expect("Must be a leaf");
+        assert_extent!(b, (16, 17));
+        assert_eq!(b.spacing, Spacing::Alone);
+    }
+
+    #[test]
+    fn expr_macro_call_token_trees_joint_punctuation() {
+        let p = qp(expression, "foo!(- --a)");
+        let m = unwrap_as!(p.value, Expression::MacroCall);
+        let spacing: Vec<_> = m.tokens.iter()
+            .map(|t| t.as_leaf().expect("Must be a leaf").spacing)
+            .collect();
+        assert_eq!(spacing, [Spacing::Alone, Spacing::Joint, Spacing::Alone, Spacing::Alone]);
+    }
+
+    #[test]
+    fn expr_macro_call_token_trees_mismatched_inner_delimiters_fail() {
+        let (err_loc, errs) = unwrap_progress_err(parse_full(expr_macro_call, "foo!(a [ b)"));
+        assert_eq!(err_loc, 6);
+        assert!(errs.contains(&Error::ExpectedRightSquare));
+    }
+
+    #[test]
+    fn expr_macro_call_token_trees_unclosed_inner_delimiters_fail() {
+        let (err_loc, errs) = unwrap_progress_err(parse_full(expr_macro_call, "foo!(a [ b"));
+        assert_eq!(err_loc, 6);
+        assert!(errs.contains(&Error::ExpectedRightSquare));
+    }
+
     #[test]
     fn expr_macro_call_with_ident() {
         let p = qp(expression, "macro_rules! foo { }");
@@ -2123,6 +2770,20 @@ mod test {
         assert_extent!(p, (0, 6))
     }
 
+    #[test]
+    fn expr_closure_pattern_with_alternation() {
+        let p = qp(expression, "|(Ok(a) | Err(a))| a");
+        assert_extent!(p, (0, 20))
+    }
+
+    #[test]
+    fn expr_closure_body_is_not_alternation() {
+        let e = qp(expression, "|a| a | b");
+        let c = unwrap_as!(e.value, Expression::Closure);
+        assert_eq!(c.args.len(), 1);
+        assert!(c.body.is_binary());
+    }
+
     #[test]
     fn expr_closure_move() {
         let p = qp(expression, "move || 42");
@@ -2191,6 +2852,29 @@ mod test {
         assert_extent!(p, (0, 15))
     }
 
+    #[test]
+    fn expr_labeled_block() {
+        let e = qp(expression, "'outer: { break 'outer 5; }");
+
+        let l = unwrap_as!(e.value, Expression::LabeledBlock);
+        assert_extent!(l, (0, 27));
+        assert_extent!(l.label, (0, 6));
+
+        let mut body = *l.body;
+        let s = body.statements.remove(0).into_expression().expect("Must be an expression");
+        let b = unwrap_as!(s.value, Expression::Break);
+        assert_extent!(b, (10, 24));
+        assert_extent!(b.label.expect("Must have a label"), (16, 22));
+        assert_extent!(b.value.expect("Must have a value"), (23, 24));
+    }
+
+    #[test]
+    fn expr_labeled_block_as_value() {
+        let p = qp(expression, "let a = 'init: { if b { break 'init 1; } 2 }");
+        assert!(p.is_let());
+        assert_extent!(p, (0, 44))
+    }
+
     #[test]
     fn expr_array_explicit() {
         let p = qp(expression, "[1, 1]");
@@ -2407,6 +3091,20 @@ mod test {
         assert_extent!(p, (0, 11))
     }
 
+    #[test]
+    fn expr_c_string() {
+        let p = qp(expression, r#"c"hello""#);
+        assert!(p.is_c_string());
+        assert_extent!(p, (0, 8))
+    }
+
+    #[test]
+    fn expr_c_string_raw() {
+        let p = qp(expression, r###"cr#"hello"#"###);
+        assert!(p.is_c_string());
+        assert_extent!(p, (0, 11))
+    }
+
     #[test]
     fn expr_byte() {
         let p = qp(expression, r#"b'a'"#);
@@ -2525,6 +3223,21 @@ mod test {
         assert_extent!(p, (0, 10))
     }
 
+    #[test]
+    fn match_arm_with_leading_pipe() {
+        let p = qp(match_arm, "| A | B => {}");
+        assert_extent!(p, (0, 13));
+        assert_extent!(p.pattern, (0, 7));
+        let a = unwrap_as!(p.pattern.kind, PatternKind::Alternation);
+        assert_eq!(a.patterns.len(), 2);
+    }
+
+    #[test]
+    fn expr_match_arm_with_leading_pipe() {
+        let p = qp(expression, "match x { | A | B => {} }");
+        assert_extent!(p, (0, 25))
+    }
+
     #[test]
     fn match_arm_with_guard() {
         let p = qp(match_arm, "a if a > 2 => 1");
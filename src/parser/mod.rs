@@ -5,7 +5,8 @@ mod expression;
 #[macro_use]
 mod test_utils;
 
-use crate::{Extent, HumanTextError};
+use crate::{Extent, HasExtent, HumanTextError, write_expected};
+use crate::delimiters::DelimiterError;
 use crate::ast::*;
 use crate::combinators::*;
 use self::{
@@ -13,11 +14,17 @@ use self::{
     expression::{
         expr_byte,
         expr_byte_string,
+        expr_c_string,
+        expr_const_block,
+        expr_disambiguation,
+        expr_literal,
         expr_macro_call,
-        expression,
+        generic_argument_const,
+        operator_infix,
         statement_expression,
     },
 };
+pub(crate) use self::expression::expression;
 use crate::tokenizer::{self, Token};
 use peresil;
 use peresil::combinators::*;
@@ -25,11 +32,14 @@ use std::{self, fmt};
 use std::collections::BTreeSet;
 
 pub(crate) type Point<'s> = TokenPoint<'s, Token>;
-pub(crate) type Master<'s> = peresil::ParseMaster<Point<'s>, Error, State>;
+pub(crate) type Master<'s> = peresil::ParseMaster<Point<'s>, Error, State<'s>>;
 pub(crate) type Progress<'s, T> = peresil::Progress<Point<'s>, T, Error>;
 
 // ------
 
+// The token offset and, within a split token, the sub-offset
+type Location = (usize, Option<u8>);
+
 /// A Point that allows splitting the tokens based on parser whims.
 ///
 /// The tokenizer greedily constructs tokens such that `>>=` will be
@@ -45,6 +55,9 @@ pub(crate) type Progress<'s, T> = peresil::Progress<Point<'s>, T, Error>;
 /// and track that we are in the middle of a split through
 /// `sub_offset`.
 ///
+/// The same happens with `x.0.1`, where the tokenizer sees `0.1` as a
+/// single floating point number.
+///
 /// This has the nice benefit of getting our automatic rewind
 /// capability from the point and the grammar logic can stay clean.
 pub(crate) struct TokenPoint<'s, T: 's> {
@@ -80,7 +93,7 @@ impl<'s, T: 's> TokenPoint<'s, T> {
         }
     }
 
-    fn location(&self) -> (usize, Option<u8>) {
+    fn location(&self) -> Location {
         (self.offset, self.sub_offset)
     }
 }
@@ -119,16 +132,85 @@ impl<'s, T> Eq for TokenPoint<'s, T> {}
 // -----
 
 #[derive(Debug, Default)]
-pub(crate) struct State {
+pub(crate) struct State<'s> {
+    // The source of the tokens, for the few checks that depend on
+    // the text of an identifier
+    text: &'s str,
     expression_ambiguity: expression::ExpressionAmbiguity,
-}
+    // Directly within the condition of an `if` or `while`
+    in_let_chain: bool,
+    recover_statements: bool,
+    statement_errors: Vec<(Extent, ErrorDetail)>,
+    contexts: Vec<(Context, Location)>,
+    furthest_failure: Option<(Location, usize, Option<Context>)>,
+}
+
+impl<'s> State<'s> {
+    pub(crate) fn new(text: &'s str) -> Self {
+        State { text, ..State::default() }
+    }
+
+    /// Malformed statements inside of blocks are skipped over and
+    /// recorded instead of failing the entire block.
+    pub(crate) fn with_statement_recovery(text: &'s str) -> Self {
+        State { text, recover_statements: true, ..State::default() }
+    }
+
+    /// The errors of each `Statement::Error`, keyed by its extent. A
+    /// block may be parsed more than once while backtracking, so
+    /// these may include errors for statements that were discarded.
+    pub(crate) fn take_statement_errors(&mut self) -> Vec<(Extent, ErrorDetail)> {
+        std::mem::take(&mut self.statement_errors)
+    }
+
+    // Remembers the innermost production that had made progress
+    // before failing, preferring the furthest failure and then the
+    // most deeply nested one.
+    fn record_failure(&mut self, pt: Point) {
+        let location = pt.location();
+        let (depth, context) = self.contexts.iter()
+            .enumerate()
+            .rev()
+            .find(|&(_, &(_, start))| start < location)
+            .map_or((0, None), |(i, &(context, _))| (i + 1, Some(context)));
+
+        let replace = match self.furthest_failure {
+            Some((furthest, furthest_depth, _)) => {
+                location > furthest || (location == furthest && depth > furthest_depth)
+            }
+            None => true,
+        };
 
-impl State {
-    pub(crate) fn new() -> Self {
-        State::default()
+        if replace {
+            self.furthest_failure = Some((location, depth, context));
+        }
     }
 
-    fn ex(&self, start: Point, end: Point) -> Extent {
+    // A failure that cannot be recovered from ends the parse, so it is
+    // the one reported even if an abandoned alternative got further.
+    fn record_fatal_failure(&mut self, pt: Point) {
+        self.furthest_failure = None;
+        self.record_failure(pt);
+    }
+
+    /// Builds the error for a failure returned by `Master::finish`.
+    /// Like the master, the failure context is then forgotten.
+    pub(crate) fn error_detail(&mut self, pt: Point, errors: Vec<Error>) -> ErrorDetail {
+        let context = match self.furthest_failure.take() {
+            Some((location, _, context)) if location == pt.location() => context,
+            _ => None,
+        };
+
+        let location = match (pt.s.first(), pt.sub_offset) {
+            (Some(&token), Some(n)) => split(token, n).map_or(token.extent().0, |(_, t)| t.extent().0),
+            (Some(token), None) => token.extent().0,
+            (None, _) => 0,
+        };
+
+        ErrorDetail { location, errors: errors.into_iter().collect(), context, delimiter: None }
+    }
+
+    pub(crate) fn ex(&self, start: Point, end: Point) -> Extent {
         use std::cmp::Ordering;
 
         // When calculating the extent of an item, we need to look
@@ -136,17 +218,22 @@ impl State {
         // use the initial point.
         let relative_tokens = start.s;
 
+        // In the middle of a split token, the position is between
+        // the head and tail of the split.
         let start_offset = |pt: Point| -> usize {
-            let Extent(a, _) = relative_tokens[0].extent();
-            let a_x = pt.sub_offset.map_or(0, |x| x + 1) as usize;
-            a + a_x
+            let token = relative_tokens[pt.offset - start.offset];
+            match pt.sub_offset {
+                Some(n) => split(token, n).expect("Cannot resume a split token").1.extent().0,
+                None => token.extent().0,
+            }
         };
 
         let end_offset = |pt: Point| -> usize {
-            let offset = pt.offset - start.offset - 1;
-            let Extent(_, b) = relative_tokens[offset].extent();
-            let b_x = pt.sub_offset.map_or(0, |x| x + 1) as usize;
-            b + b_x
+            let offset = pt.offset - start.offset;
+            match pt.sub_offset {
+                Some(n) => split(relative_tokens[offset], n).expect("Cannot resume a split token").0.extent().1,
+                None => relative_tokens[offset - 1].extent().1,
+            }
         };
 
         match start.offset.cmp(&end.offset) {
@@ -159,7 +246,7 @@ impl State {
                 match start.sub_offset.cmp(&end.sub_offset) {
                     Ordering::Less => {
                         let a = start_offset(start);
-                        let b = start_offset(end);
+                        let b = end_offset(end);
                         Extent(a, b)
                     }
                     Ordering::Equal => {
@@ -174,9 +261,11 @@ impl State {
     }
 }
 
-// define an error type - emphasis on errors. Need to implement Recoverable (more to discuss.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub(crate) enum Error {
+/// A single reason that parsing failed, usually something that was
+/// expected at the failure location.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Error {
     ExpectedAmpersand,
     ExpectedAmpersandEquals,
     ExpectedAs,
@@ -184,7 +273,7 @@ pub(crate) enum Error {
     ExpectedAsync,
     ExpectedAt,
     ExpectedAuto,
-    #[allow(unused)]
+    ExpectedAwait,
     ExpectedBackslash,
     ExpectedBang,
     ExpectedBox,
@@ -192,6 +281,8 @@ pub(crate) enum Error {
     ExpectedByte,
     ExpectedByteString,
     ExpectedByteStringRaw,
+    ExpectedCString,
+    ExpectedCStringRaw,
     ExpectedCaret,
     ExpectedCaretEquals,
     ExpectedCharacter,
@@ -207,7 +298,6 @@ pub(crate) enum Error {
     ExpectedDocCommentInnerLine,
     ExpectedDocCommentOuterBlock,
     ExpectedDocCommentOuterLine,
-    #[allow(unused)]
     ExpectedDollar,
     ExpectedDoubleAmpersand,
     ExpectedDoubleColon,
@@ -218,6 +308,7 @@ pub(crate) enum Error {
     ExpectedDoublePipe,
     ExpectedDoubleRightAngle,
     ExpectedElse,
+    ExpectedEndOfFile,
     ExpectedEnum,
     ExpectedEquals,
     ExpectedExtern,
@@ -237,6 +328,7 @@ pub(crate) enum Error {
     ExpectedLet,
     ExpectedLifetime,
     ExpectedLoop,
+    ExpectedMacroRules,
     ExpectedMatch,
     ExpectedMinus,
     ExpectedMinusEquals,
@@ -256,6 +348,7 @@ pub(crate) enum Error {
     ExpectedQuestionMark,
     ExpectedRef,
     ExpectedReturn,
+    ExpectedSafe,
     ExpectedRightAngle,
     ExpectedRightCurly,
     ExpectedRightParen,
@@ -271,7 +364,6 @@ pub(crate) enum Error {
     ExpectedStruct,
     ExpectedThickArrow,
     ExpectedThinArrow,
-    #[allow(unused)]
     ExpectedTilde,
     ExpectedTimesEquals,
     ExpectedTrait,
@@ -284,12 +376,283 @@ pub(crate) enum Error {
     ExpectedWhile,
 
     ExpectedExpression,
+    ExpectedToken,
 
     BlockNotAllowedHere,
+    InvalidLetElseInitializer,
+    InvalidLetCondition,
+    ExpectedMacroRule,
+    ExpectedMacroRepetitionOperator,
+    InvalidFragmentSpecifier,
 }
 
 impl peresil::Recoverable for Error {
-    fn recoverable(&self) -> bool { true }
+    fn recoverable(&self) -> bool {
+        match *self {
+            // We've already committed to a `let ... else`; there's
+            // no other way to parse it.
+            Error::InvalidLetElseInitializer => false,
+            // We've already committed to the `if` or `while`
+            Error::InvalidLetCondition => false,
+            // We've already committed to a `macro_rules!` definition
+            // or to a `$(...)` repetition.
+            Error::ExpectedMacroRule |
+            Error::ExpectedMacroRepetitionOperator |
+            Error::InvalidFragmentSpecifier => false,
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match *self {
+            Error::ExpectedAmpersand               => "`&`",
+            Error::ExpectedAmpersandEquals         => "`&=`",
+            Error::ExpectedAs                      => "`as`",
+            Error::ExpectedAsterisk                => "`*`",
+            Error::ExpectedAsync                   => "`async`",
+            Error::ExpectedAt                      => "`@`",
+            Error::ExpectedAuto                    => "`auto`",
+            Error::ExpectedAwait                   => "`await`",
+            Error::ExpectedBackslash               => "`\\`",
+            Error::ExpectedBang                    => "`!`",
+            Error::ExpectedBox                     => "`box`",
+            Error::ExpectedBreak                   => "`break`",
+            Error::ExpectedByte                    => "a byte literal",
+            Error::ExpectedByteString              => "a byte string literal",
+            Error::ExpectedByteStringRaw           => "a raw byte string literal",
+            Error::ExpectedCString                 => "a C string literal",
+            Error::ExpectedCStringRaw              => "a raw C string literal",
+            Error::ExpectedCaret                   => "`^`",
+            Error::ExpectedCaretEquals             => "`^=`",
+            Error::ExpectedCharacter               => "a character literal",
+            Error::ExpectedColon                   => "`:`",
+            Error::ExpectedComma                   => "`,`",
+            Error::ExpectedConst                   => "`const`",
+            Error::ExpectedContinue                => "`continue`",
+            Error::ExpectedCrate                   => "`crate`",
+            Error::ExpectedDefault                 => "`default`",
+            Error::ExpectedDyn                     => "`dyn`",
+            Error::ExpectedDivideEquals            => "`/=`",
+            Error::ExpectedDocCommentInnerBlock    => "`/*!`",
+            Error::ExpectedDocCommentInnerLine     => "`//!`",
+            Error::ExpectedDocCommentOuterBlock    => "`/**`",
+            Error::ExpectedDocCommentOuterLine     => "`///`",
+            Error::ExpectedDollar                  => "`$`",
+            Error::ExpectedDoubleAmpersand         => "`&&`",
+            Error::ExpectedDoubleColon             => "`::`",
+            Error::ExpectedDoubleEquals            => "`==`",
+            Error::ExpectedDoubleLeftAngle         => "`<<`",
+            Error::ExpectedDoublePeriod            => "`..`",
+            Error::ExpectedDoublePeriodEquals      => "`..=`",
+            Error::ExpectedDoublePipe              => "`||`",
+            Error::ExpectedDoubleRightAngle        => "`>>`",
+            Error::ExpectedElse                    => "`else`",
+            Error::ExpectedEndOfFile               => "the end of the input",
+            Error::ExpectedEnum                    => "`enum`",
+            Error::ExpectedEquals                  => "`=`",
+            Error::ExpectedExtern                  => "`extern`",
+            Error::ExpectedFn                      => "`fn`",
+            Error::ExpectedFor                     => "`for`",
+            Error::ExpectedGreaterThanOrEquals     => "`>=`",
+            Error::ExpectedHash                    => "`#`",
+            Error::ExpectedIdent                   => "an identifier",
+            Error::ExpectedIf                      => "`if`",
+            Error::ExpectedImpl                    => "`impl`",
+            Error::ExpectedIn                      => "`in`",
+            Error::ExpectedLeftAngle               => "`<`",
+            Error::ExpectedLeftCurly               => "`{`",
+            Error::ExpectedLeftParen               => "`(`",
+            Error::ExpectedLeftSquare              => "`[`",
+            Error::ExpectedLessThanOrEquals        => "`<=`",
+            Error::ExpectedLet                     => "`let`",
+            Error::ExpectedLifetime                => "a lifetime",
+            Error::ExpectedLoop                    => "`loop`",
+            Error::ExpectedMacroRules              => "`macro_rules`",
+            Error::ExpectedMatch                   => "`match`",
+            Error::ExpectedMinus                   => "`-`",
+            Error::ExpectedMinusEquals             => "`-=`",
+            Error::ExpectedMod                     => "`mod`",
+            Error::ExpectedMove                    => "`move`",
+            Error::ExpectedMut                     => "`mut`",
+            Error::ExpectedNotEqual                => "`!=`",
+            Error::ExpectedNumber                  => "a number",
+            Error::ExpectedPercent                 => "`%`",
+            Error::ExpectedPercentEquals           => "`%=`",
+            Error::ExpectedPeriod                  => "`.`",
+            Error::ExpectedPipe                    => "`|`",
+            Error::ExpectedPipeEquals              => "`|=`",
+            Error::ExpectedPlus                    => "`+`",
+            Error::ExpectedPlusEquals              => "`+=`",
+            Error::ExpectedPub                     => "`pub`",
+            Error::ExpectedQuestionMark            => "`?`",
+            Error::ExpectedRef                     => "`ref`",
+            Error::ExpectedReturn                  => "`return`",
+            Error::ExpectedSafe                    => "`safe`",
+            Error::ExpectedRightAngle              => "`>`",
+            Error::ExpectedRightCurly              => "`}`",
+            Error::ExpectedRightParen              => "`)`",
+            Error::ExpectedRightSquare             => "`]`",
+            Error::ExpectedSelfIdent               => "`self`",
+            Error::ExpectedSemicolon               => "`;`",
+            Error::ExpectedShiftLeftEquals         => "`<<=`",
+            Error::ExpectedShiftRightEquals        => "`>>=`",
+            Error::ExpectedSlash                   => "`/`",
+            Error::ExpectedStatic                  => "`static`",
+            Error::ExpectedString                  => "a string literal",
+            Error::ExpectedStringRaw               => "a raw string literal",
+            Error::ExpectedStruct                  => "`struct`",
+            Error::ExpectedThickArrow              => "`=>`",
+            Error::ExpectedThinArrow               => "`->`",
+            Error::ExpectedTilde                   => "`~`",
+            Error::ExpectedTimesEquals             => "`*=`",
+            Error::ExpectedTrait                   => "`trait`",
+            Error::ExpectedTriplePeriod            => "`...`",
+            Error::ExpectedType                    => "`type`",
+            Error::ExpectedUnion                   => "`union`",
+            Error::ExpectedUnsafe                  => "`unsafe`",
+            Error::ExpectedUse                     => "`use`",
+            Error::ExpectedWhere                   => "`where`",
+            Error::ExpectedWhile                   => "`while`",
+            Error::ExpectedExpression              => "an expression",
+            Error::ExpectedToken                   => "any token",
+            Error::BlockNotAllowedHere             => "an expression other than a block",
+            Error::InvalidLetElseInitializer       => "a `let ... else` initializer that is not an `&&` or `||` expression and does not end with `}`",
+            Error::InvalidLetCondition             => "a `let` condition that is not an operand of `||`",
+            Error::ExpectedMacroRule               => "a macro rule",
+            Error::ExpectedMacroRepetitionOperator => "a repetition operator (`*`, `+`, or `?`)",
+            Error::InvalidFragmentSpecifier        => "a valid fragment specifier",
+        };
+        f.write_str(description)
+    }
+}
+
+/// A grammar production that was being parsed when an error occurred
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Production {
+    Attribute,
+    CallArguments,
+    ClosureArguments,
+    EnumVariants,
+    FunctionArguments,
+    FunctionBody,
+    GenericDeclarations,
+    ImplBody,
+    MatchArms,
+    ModuleBody,
+    StructFields,
+    TraitBody,
+    WhereClause,
+}
+
+impl fmt::Display for Production {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match *self {
+            Production::Attribute           => "attribute",
+            Production::CallArguments       => "call arguments",
+            Production::ClosureArguments    => "closure arguments",
+            Production::EnumVariants        => "enum variants",
+            Production::FunctionArguments   => "function arguments",
+            Production::FunctionBody        => "function body",
+            Production::GenericDeclarations => "generic declarations",
+            Production::ImplBody            => "impl body",
+            Production::MatchArms           => "match arms",
+            Production::ModuleBody          => "module body",
+            Production::StructFields        => "struct fields",
+            Production::TraitBody           => "trait body",
+            Production::WhereClause         => "where clause",
+        };
+        f.write_str(description)
+    }
+}
+
+/// The innermost production that had been partially parsed when an
+/// error occurred, such as the arguments of a specific function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Context {
+    production: Production,
+    name: Option<Extent>,
+}
+
+impl Context {
+    pub(crate) fn new(production: Production, name: Option<Extent>) -> Self {
+        Context { production, name }
+    }
+
+    /// The production that was being parsed
+    pub fn production(&self) -> Production {
+        self.production
+    }
+
+    /// The name of the item that the production belongs to, if any
+    pub fn name(&self) -> Option<Extent> {
+        self.name
+    }
+}
+
+/// A higher-level description of something that was expected,
+/// grouping together the individual tokens that could begin it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Expected {
+    Item,
+    Expression,
+    Type,
+    Pattern,
+    Operator,
+    Identifier,
+    ClosingDelimiter,
+    Error(Error),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Expected::Item             => f.write_str("an item"),
+            Expected::Expression       => f.write_str("an expression"),
+            Expected::Type             => f.write_str("a type"),
+            Expected::Pattern          => f.write_str("a pattern"),
+            Expected::Operator         => f.write_str("an operator"),
+            Expected::Identifier       => f.write_str("an identifier"),
+            Expected::ClosingDelimiter => f.write_str("a closing delimiter"),
+            Expected::Error(e)         => e.fmt(f),
+        }
+    }
+}
+
+// Each category is recognized by the tokens its parser expected when
+// failing on the very first token.
+fn expected_categories() -> &'static [(Expected, BTreeSet<Error>)] {
+    use std::sync::OnceLock;
+
+    static CATEGORIES: OnceLock<Vec<(Expected, BTreeSet<Error>)>> = OnceLock::new();
+
+    fn first_errors<F, T>(f: F) -> BTreeSet<Error>
+        where F: for<'s> FnOnce(&mut Master<'s>, Point<'s>) -> Progress<'s, T>
+    {
+        let tokens = [Token::EndOfFile(Extent(0, 0))];
+        let mut pm = Master::with_state(State::new(""));
+        let r = f(&mut pm, Point::new(&tokens));
+        match pm.finish(r).status {
+            peresil::Status::Success(_) => BTreeSet::new(),
+            peresil::Status::Failure(e) => {
+                e.into_iter().filter(|&e| e != Error::ExpectedExpression).collect()
+            }
+        }
+    }
+
+    CATEGORIES.get_or_init(|| {
+        vec![
+            (Expected::Item, first_errors(|pm, pt| attributed(item)(pm, pt))),
+            (Expected::Expression, first_errors(expression)),
+            (Expected::Type, first_errors(typ)),
+            (Expected::Pattern, first_errors(pattern)),
+            (Expected::Operator, first_errors(operator_infix)),
+            (Expected::Identifier, first_errors(ident)),
+        ]
+    })
 }
 
 /// Information about a parsing error
@@ -297,6 +660,9 @@ impl peresil::Recoverable for Error {
 pub struct ErrorDetail {
     pub(crate) location: usize,
     pub(crate) errors: BTreeSet<Error>,
+    pub(crate) context: Option<Context>,
+    // Boxed to keep the error small, as it is rarely present
+    pub(crate) delimiter: Option<Box<DelimiterError>>,
 }
 
 impl ErrorDetail {
@@ -304,8 +670,86 @@ impl ErrorDetail {
     pub fn with_text<'a>(&'a self, text: &'a str) -> ErrorDetailText<'a> {
         ErrorDetailText { detail: self, text }
     }
+
+    /// The byte offset into the source code where parsing failed
+    pub fn location(&self) -> usize {
+        self.location
+    }
+
+    /// Everything that would have allowed parsing to continue
+    pub fn expected(&self) -> &BTreeSet<Error> {
+        &self.errors
+    }
+
+    /// The expected errors, with the tokens that could begin an
+    /// item, expression, type, pattern, infix operator, or identifier
+    /// grouped together, as well as multiple closing delimiters.
+    pub fn expected_summary(&self) -> Vec<Expected> {
+        let categories = expected_categories();
+        let matched: Vec<_> = categories.iter()
+            .filter(|(_, first)| !first.is_empty() && first.is_subset(&self.errors))
+            .collect();
+
+        // A pattern can begin with a subset of the tokens that can
+        // begin an expression; only report the broadest category.
+        let mut remaining = self.errors.clone();
+        let mut summary = Vec::new();
+
+        for &&(category, ref first) in &matched {
+            remaining.retain(|e| !first.contains(e));
+
+            let is_narrower = matched.iter()
+                .any(|(_, other)| other != first && first.is_subset(other));
+            if !is_narrower {
+                summary.push(category);
+            }
+        }
+
+        if summary.contains(&Expected::Expression) {
+            remaining.remove(&Error::ExpectedExpression);
+        }
+
+        let is_closing = |e: &Error| matches!(*e,
+            Error::ExpectedRightCurly | Error::ExpectedRightParen | Error::ExpectedRightSquare
+        );
+        if remaining.iter().filter(|e| is_closing(e)).count() > 1 {
+            summary.push(Expected::ClosingDelimiter);
+            remaining.retain(|e| !is_closing(e));
+        }
+
+        summary.extend(remaining.into_iter().map(Expected::Error));
+        summary
+    }
+
+    /// The innermost production that was being parsed, if known
+    pub fn context(&self) -> Option<Context> {
+        self.context
+    }
+
+    /// An unbalanced delimiter that probably caused the failure, if
+    /// any was found
+    pub fn delimiter(&self) -> Option<DelimiterError> {
+        self.delimiter.as_deref().copied()
+    }
 }
 
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unable to parse text at byte {}", self.location)?;
+        if let Some(context) = self.context {
+            write!(f, " while parsing {}", context.production)?;
+        }
+        f.write_str(", expected ")?;
+        write_expected(f, self.expected_summary())?;
+        if let Some(delimiter) = &self.delimiter {
+            write!(f, ". {}", delimiter)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ErrorDetail {}
+
 /// Information about a parsing error including original source code
 #[derive(Debug)]
 pub struct ErrorDetailText<'a> {
@@ -317,17 +761,39 @@ impl<'a> fmt::Display for ErrorDetailText<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let human = HumanTextError::new(self.text, self.detail.location);
 
-        writeln!(f, "Unable to parse text (line {}, column {})", human.line, human.column)?;
+        write!(f, "Unable to parse text (line {}, column {})", human.line, human.column)?;
+        if let Some(context) = self.detail.context {
+            write!(f, " while parsing {}", context.production)?;
+            if let Some(name) = context.name {
+                write!(f, " of `{}`", &self.text[name])?;
+            }
+        }
+        writeln!(f)?;
         writeln!(f, "{}{}", human.head_of_line, human.tail_of_line)?;
         writeln!(f, "{:>width$}", "^", width = human.column)?;
         writeln!(f, "Expected:")?;
-        for e in &self.detail.errors {
-            writeln!(f, "  {:?}", e)?; // TODO: should be Display
+        for e in self.detail.expected_summary() {
+            writeln!(f, "  {}", e)?;
+        }
+        if let Some(delimiter) = &self.detail.delimiter {
+            writeln!(f, "{}", delimiter.with_text(self.text))?;
         }
         Ok(())
     }
 }
 
+/// Parses a piece of code that must make up the entire input
+pub(crate) fn fragment<'s, F, T>(f: F) -> impl FnOnce(&mut Master<'s>, Point<'s>) -> Progress<'s, T>
+    where F: FnOnce(&mut Master<'s>, Point<'s>) -> Progress<'s, T>
+{
+    move |pm, pt| {
+        sequence!(pm, pt, {
+            value = f;
+            _     = end_of_file;
+        }, |_, _| value)
+    }
+}
+
 pub(crate) fn item<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Item> {
     pm.alternate(pt)
         .one(map(attribute_containing, Item::AttributeContaining))
@@ -335,6 +801,7 @@ pub(crate) fn item<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Item>
         .one(map(extern_crate, Item::ExternCrate))
         .one(map(extern_block, Item::ExternBlock))
         .one(map(function, Item::Function))
+        .one(map(macro_rules, Item::MacroRules))
         .one(map(item_macro_call, Item::MacroCall))
         .one(map(module, Item::Module))
         .one(map(p_enum, Item::Enum))
@@ -342,6 +809,7 @@ pub(crate) fn item<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Item>
         .one(map(p_static, Item::Static))
         .one(map(p_struct, Item::Struct))
         .one(map(p_trait, Item::Trait))
+        .one(map(trait_alias, Item::TraitAlias))
         .one(map(p_union, Item::Union))
         .one(map(p_use, Item::Use))
         .one(map(type_alias, Item::TypeAlias))
@@ -367,8 +835,8 @@ macro_rules! shims {
 
 shims! [
     // Match up these names better
-    (ident_normal, Token::into_ident, Error::ExpectedIdent),
-    (lifetime_normal, Token::into_lifetime, Error::ExpectedLifetime),
+    (ident_normal, Token::into_ident, Error::ExpectedIdent, tokenizer::Ident),
+    (lifetime_normal, Token::into_lifetime, Error::ExpectedLifetime, tokenizer::Ident),
     (number_normal, Token::into_number, Error::ExpectedNumber, tokenizer::Number),
 
     (character, Token::into_character, Error::ExpectedCharacter),
@@ -377,11 +845,14 @@ shims! [
     (byte, Token::into_byte, Error::ExpectedByte),
     (byte_string, Token::into_byte_string, Error::ExpectedByteString),
     (byte_string_raw, Token::into_byte_string_raw, Error::ExpectedByteStringRaw),
+    (c_string, Token::into_c_string, Error::ExpectedCString),
+    (c_string_raw, Token::into_c_string_raw, Error::ExpectedCStringRaw),
 
     // Keywords
     (kw_as, Token::into_as, Error::ExpectedAs),
     (kw_async, Token::into_async, Error::ExpectedAsync),
     (kw_auto, Token::into_auto, Error::ExpectedAuto),
+    (kw_await, Token::into_await, Error::ExpectedAwait),
     (kw_box, Token::into_box, Error::ExpectedBox),
     (kw_break, Token::into_break, Error::ExpectedBreak),
     (kw_const, Token::into_const, Error::ExpectedConst),
@@ -399,6 +870,7 @@ shims! [
     (kw_in, Token::into_in, Error::ExpectedIn),
     (kw_let, Token::into_let, Error::ExpectedLet),
     (kw_loop, Token::into_loop, Error::ExpectedLoop),
+    (kw_macro_rules, Token::into_macro_rules, Error::ExpectedMacroRules),
     (kw_match, Token::into_match, Error::ExpectedMatch),
     (kw_mod, Token::into_mod, Error::ExpectedMod),
     (kw_move, Token::into_move, Error::ExpectedMove),
@@ -406,6 +878,7 @@ shims! [
     (kw_pub, Token::into_pub, Error::ExpectedPub),
     (kw_ref, Token::into_ref, Error::ExpectedRef),
     (kw_return, Token::into_return, Error::ExpectedReturn),
+    (kw_safe, Token::into_safe, Error::ExpectedSafe),
     (kw_self_ident, Token::into_self_ident, Error::ExpectedSelfIdent),
     (kw_static, Token::into_static, Error::ExpectedStatic),
     (kw_struct, Token::into_struct, Error::ExpectedStruct),
@@ -432,6 +905,7 @@ shims! [
     (ampersand_equals, Token::into_ampersand_equals, Error::ExpectedAmpersandEquals),
     (asterisk, Token::into_asterisk, Error::ExpectedAsterisk),
     (at, Token::into_at, Error::ExpectedAt),
+    (dollar, Token::into_dollar, Error::ExpectedDollar),
     (bang, Token::into_bang, Error::ExpectedBang),
     (caret, Token::into_caret, Error::ExpectedCaret),
     (caret_equals, Token::into_caret_equals, Error::ExpectedCaretEquals),
@@ -446,6 +920,7 @@ shims! [
     (double_period_equals, Token::into_double_period_equals, Error::ExpectedDoublePeriodEquals),
     (double_pipe, Token::into_double_pipe, Error::ExpectedDoublePipe),
     (double_right_angle, Token::into_double_right_angle, Error::ExpectedDoubleRightAngle),
+    (end_of_file, Token::into_end_of_file, Error::ExpectedEndOfFile),
     (equals, Token::into_equals, Error::ExpectedEquals),
     (greater_than_or_equals, Token::into_greater_than_or_equals, Error::ExpectedGreaterThanOrEquals),
     (hash, Token::into_hash, Error::ExpectedHash),
@@ -480,10 +955,13 @@ fn token<'s, F, T>(token_convert: F, error: Error) ->
     impl FnOnce(&mut Master<'s>, Point<'s>) -> Progress<'s, T>
     where F: Fn(Token) -> Option<T>
 {
-    move |_, pt| {
+    move |pm, pt| {
         let original_token = match pt.s.first() {
             Some(&token) => token,
-            None => return Progress::failure(pt, error),
+            None => {
+                pm.state.record_failure(pt);
+                return Progress::failure(pt, error);
+            }
         };
 
         let token = match pt.sub_offset {
@@ -494,10 +972,20 @@ fn token<'s, F, T>(token_convert: F, error: Error) ->
         };
 
         match token_convert(token) {
-            Some(v) => {
+            Some(v) if token.extent().1 == original_token.extent().1 => {
                 // We exactly matched the requested token
                 Progress::success(pt.advance_by(1), v)
             }
+            Some(v) => {
+                // We matched the tail of a split, but there is more
+                // of the original token after it
+                let sub_offset = pt.sub_offset.map_or(0, |x| x + 1);
+                let pt = Point {
+                    sub_offset: Some(sub_offset),
+                    ..pt
+                };
+                Progress::success(pt, v)
+            }
             None => {
                 // Maybe we can split the token
                 let sub_offset = pt.sub_offset.map(|x| x + 1).unwrap_or(0);
@@ -514,12 +1002,14 @@ fn token<'s, F, T>(token_convert: F, error: Error) ->
                             }
                             None => {
                                 // The split did not match
+                                pm.state.record_failure(pt);
                                 Progress::failure(pt, error)
                             }
                         }
                     }
                     None => {
                         // Cannot split
+                        pm.state.record_failure(pt);
                         Progress::failure(pt, error)
                     }
                 }
@@ -572,6 +1062,18 @@ fn split(token: Token, n: u8) -> Option<(Token, Token)> {
             let b = Token::Ampersand(Extent(s+1, e));
             Some((a, b))
         }
+        (Token::Number(tokenizer::Number::Decimal(n)), 0) => {
+            let (before, period, _) = n.split_tuple_indices()?;
+            let a = Token::Number(tokenizer::Number::Decimal(before));
+            let b = Token::Period(period);
+            Some((a, b))
+        }
+        (Token::Number(tokenizer::Number::Decimal(n)), 1) => {
+            let (_, period, after) = n.split_tuple_indices()?;
+            let a = Token::Period(period);
+            let b = Token::Number(tokenizer::Number::Decimal(after?));
+            Some((a, b))
+        }
         _ => None
     }
 }
@@ -580,7 +1082,7 @@ fn function<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Function> {
     sequence!(pm, pt, {
         spt    = point;
         header = function_header;
-        body   = block;
+        body   = named_context(Production::FunctionBody, header.name.extent, block);
     }, |pm: &mut Master, pt| Function {
         extent: pm.state.ex(spt, pt),
         header,
@@ -597,7 +1099,7 @@ fn function_header<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Funct
         _           = kw_fn;
         name        = ident;
         generics    = optional(generic_declarations);
-        arguments   = function_arglist;
+        arguments   = named_context(Production::FunctionArguments, name.extent, function_arglist);
         return_type = optional(function_return_type);
         wheres      = optional(where_clause);
     }, |pm: &mut Master, pt| {
@@ -657,31 +1159,40 @@ fn function_qualifier_extern<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
 fn ident<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Ident> {
     pm.alternate(pt)
         // Contextual or edition keywords
-        .one(kw_default)
-        .one(kw_self_ident)
-        .one(kw_dyn)
-        .one(kw_union)
-        .one(ident_normal)
+        .one(map(kw_default, Ident::from))
+        .one(map(kw_self_ident, Ident::from))
+        .one(map(kw_dyn, Ident::from))
+        .one(map(kw_union, Ident::from))
+        .one(map(kw_macro_rules, Ident::from))
+        .one(map(kw_safe, Ident::from))
+        .one(map(ident_normal, |i| Ident { extent: i.extent, name: i.name, is_raw: i.is_raw }))
         .finish()
-        .map(|extent| Ident { extent })
         .map_err(|_| Error::ExpectedIdent)
 }
 
 fn generic_declarations<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, GenericDeclarations> {
     sequence!(pm, pt, {
-        spt       = point;
-        _         = left_angle;
-        lifetimes = zero_or_more_tailed_values(comma, attributed(generic_declaration_lifetime));
-        types     = zero_or_more_tailed_values(comma, attributed(generic_declaration_type));
-        _         = right_angle;
+        spt     = point;
+        _       = left_angle;
+        members = context(Production::GenericDeclarations, zero_or_more_tailed_values(comma, attributed(generic_declaration)));
+        _       = right_angle;
     }, |pm: &mut Master, pt| GenericDeclarations {
         extent: pm.state.ex(spt, pt),
-        lifetimes,
-        types,
+        members,
         whitespace: Vec::new(),
     })
 }
 
+// Parsing all of these equally is more permissive than the compiler,
+// which requires lifetimes to come first.
+fn generic_declaration<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, GenericDeclaration> {
+    pm.alternate(pt)
+        .one(map(generic_declaration_lifetime, GenericDeclaration::Lifetime))
+        .one(map(generic_declaration_const, GenericDeclaration::Const))
+        .one(map(generic_declaration_type, GenericDeclaration::Type))
+        .finish()
+}
+
 fn generic_declaration_lifetime<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, GenericDeclarationLifetime> {
     sequence!(pm, pt, {
         spt        = point;
@@ -732,6 +1243,30 @@ fn generic_declaration_type_default<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> P
     }, |_, _| typ)
 }
 
+fn generic_declaration_const<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, GenericDeclarationConst> {
+    sequence!(pm, pt, {
+        spt     = point;
+        _       = kw_const;
+        name    = ident;
+        _       = colon;
+        typ     = typ;
+        default = optional(generic_declaration_const_default);
+    }, |pm: &mut Master, pt| GenericDeclarationConst {
+        extent: pm.state.ex(spt, pt),
+        name,
+        typ,
+        default,
+        whitespace: Vec::new(),
+    })
+}
+
+fn generic_declaration_const_default<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Expression> {
+    sequence!(pm, pt, {
+        _       = equals;
+        default = generic_argument_const;
+    }, |_, _| default)
+}
+
 fn function_arglist<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Vec<Argument>> {
     sequence!(pm, pt, {
         _        = left_paren;
@@ -759,7 +1294,7 @@ fn self_argument_longhand<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s
     }, |pm: &mut Master, pt| SelfArgumentLonghand {
         extent: pm.state.ex(spt, pt),
         is_mut,
-        name: Ident { extent: name },
+        name: Ident::from(name),
         typ,
         whitespace: Vec::new(),
     })
@@ -774,7 +1309,7 @@ fn self_argument_shorthand<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'
     }, |pm: &mut Master, pt| SelfArgumentShorthand {
         extent: pm.state.ex(spt, pt),
         qualifier,
-        name: Ident { extent: name },
+        name: Ident::from(name),
         whitespace: Vec::new(),
     })
 }
@@ -791,7 +1326,7 @@ fn self_argument_qualifier<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
 fn function_argument<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Argument> {
     sequence!(pm, pt, {
         spt  = point;
-        name = pattern;
+        name = pattern_single;
         _    = colon;
         typ  = typ;
     }, |pm: &mut Master, pt| Argument::Named(NamedArgument {
@@ -812,7 +1347,7 @@ fn function_return_type<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s,
 fn where_clause<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Vec<Where>> {
     sequence!(pm, pt, {
         _ = kw_where;
-        w = one_or_more_tailed_values(comma, where_clause_item);
+        w = context(Production::WhereClause, one_or_more_tailed_values(comma, where_clause_item));
     }, |_, _| w)
 }
 
@@ -920,11 +1455,12 @@ fn trait_bound_relaxed<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, T
     }, |pm: &mut Master, pt| TraitBoundRelaxed { extent: pm.state.ex(spt, pt), typ, whitespace: Vec::new() })
 }
 
-fn block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Block> {
+pub(crate) fn block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Block> {
     sequence!(pm, pt, {
         spt               = point;
         _                 = left_curly;
-        (mut stmts, term) = zero_or_more_implicitly_tailed_values_terminated(semicolon, statement);
+        attributes        = zero_or_more(attribute_containing);
+        (mut stmts, term) = block_statements;
         _                 = right_curly;
     }, |pm: &mut Master, pt| {
         let expr = if !term && stmts.last().map_or(false, Statement::is_expression) {
@@ -935,6 +1471,7 @@ fn block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Block> {
 
         Block {
             extent: pm.state.ex(spt, pt),
+            attributes,
             statements: stmts,
             expression: expr,
             whitespace: Vec::new(),
@@ -942,6 +1479,101 @@ fn block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Block> {
     })
 }
 
+fn block_statements<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, (Vec<Statement>, bool)> {
+    if !pm.state.recover_statements {
+        return zero_or_more_implicitly_tailed_values_terminated(semicolon, statement)(pm, pt);
+    }
+
+    let mut tailed = Tailed::default();
+    let mut pt = pt;
+
+    loop {
+        let start = pt;
+        let (next_pt, next_tailed) = try_parse!(zero_or_more_implicitly_tailed_append(tailed, semicolon, statement)(pm, pt));
+        pt = next_pt;
+        tailed = next_tailed;
+
+        match pt.s.first() {
+            Some(Token::RightCurly(_)) | Some(Token::EndOfFile(_)) | None => break,
+            _ => {}
+        }
+
+        let detail = statements_error_detail(pm, start, pt);
+
+        let end = skip_malformed(pt, true);
+        let extent = pm.state.ex(pt, end);
+        pm.state.statement_errors.push((extent, detail));
+
+        tailed.values.push(Statement::Error(extent));
+        tailed.last_had_separator = false;
+        pt = end;
+    }
+
+    Progress::success(pt, (tailed.values, tailed.last_had_separator))
+}
+
+// Reports whatever stopped the statements, just as if we weren't
+// recovering. Finishing a master discards all of its failures, which
+// the enclosing item still needs, so the statements are parsed again
+// with a separate master.
+fn statements_error_detail<'s>(pm: &mut Master<'s>, start: Point<'s>, stop: Point<'s>) -> ErrorDetail {
+    let mut probe = Master::with_state(std::mem::take(&mut pm.state));
+    let furthest_failure = probe.state.furthest_failure.take();
+
+    let statements = zero_or_more_implicitly_tailed_values(semicolon, statement)(&mut probe, start);
+    let stopped = match statements.status {
+        peresil::Status::Success(_) => right_curly(&mut probe, stop),
+        peresil::Status::Failure(e) => Progress::failure(statements.point, e),
+    };
+    let failure = probe.finish(stopped);
+
+    let detail = match failure.status {
+        peresil::Status::Failure(errors) => probe.state.error_detail(failure.point, errors),
+        peresil::Status::Success(_) => probe.state.error_detail(stop, vec![Error::ExpectedRightCurly]),
+    };
+
+    pm.state = probe.state;
+    pm.state.furthest_failure = furthest_failure;
+    detail
+}
+
+/// Skips over malformed source, balancing delimiters along the way.
+///
+/// Stops after a `;` at the starting depth, or before a `}` that
+/// would close the enclosing block when `inside_block` is set.
+/// Otherwise, a `}` that returns to the starting depth ends the
+/// malformed source, as it would for an item.
+pub(crate) fn skip_malformed(pt: Point<'_>, inside_block: bool) -> Point<'_> {
+    let mut depth = 0usize;
+    // Never resume in the middle of a split token
+    let skip = if pt.sub_offset.is_some() { 1 } else { 0 };
+
+    for (i, token) in pt.s.iter().enumerate().skip(skip) {
+        match *token {
+            Token::EndOfFile(_) => return pt.advance_by(i),
+            Token::LeftCurly(_) | Token::LeftParen(_) | Token::LeftSquare(_) => depth += 1,
+            Token::RightCurly(_) if depth == 0 && inside_block => return pt.advance_by(i),
+            Token::RightCurly(_) if depth <= 1 && !inside_block => return pt.advance_by(i + 1),
+            Token::RightCurly(_) | Token::RightParen(_) | Token::RightSquare(_) => {
+                depth = depth.saturating_sub(1)
+            }
+            Token::Semicolon(_) if depth == 0 => return pt.advance_by(i + 1),
+            _ => {}
+        }
+    }
+
+    pt.advance_by(pt.s.len())
+}
+
+// Outside of a block, a statement may be followed by the semicolon
+// that would otherwise separate it from the next statement.
+pub(crate) fn statement_alone<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Statement> {
+    sequence!(pm, pt, {
+        statement = statement;
+        _         = optional(semicolon);
+    }, |_, _| statement)
+}
+
 fn statement<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Statement> {
     pm.alternate(pt)
         .one(map(attributed(item), Statement::Item))
@@ -963,6 +1595,7 @@ impl ImplicitSeparator for Statement {
             Statement::Expression(ref e) => e.may_terminate_statement(),
             Statement::Item(_)           => true,
             Statement::Empty(_)          => false,
+            Statement::Error(_)          => true,
         }
     }
 }
@@ -980,45 +1613,321 @@ fn item_macro_call<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Macro
         extent: pm.state.ex(spt, pt),
         name,
         arg,
-        args,
+        args: args.0,
+        tokens: args.1,
         whitespace: Vec::new(),
     })
 }
 
-fn item_macro_call_args<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MacroCallArgs> {
+fn item_macro_call_args<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (MacroCallArgs, Vec<TokenTree>)>
+{
     pm.alternate(pt)
-        .one(map(item_macro_call_paren, MacroCallArgs::Paren))
-        .one(map(item_macro_call_square, MacroCallArgs::Square))
-        .one(map(item_macro_call_curly, MacroCallArgs::Curly))
+        .one(map(item_macro_call_paren, |(e, t)| (MacroCallArgs::Paren(e), t)))
+        .one(map(item_macro_call_square, |(e, t)| (MacroCallArgs::Square(e), t)))
+        .one(map(item_macro_call_curly, |(e, t)| (MacroCallArgs::Curly(e), t)))
         .finish()
 }
 
-fn item_macro_call_paren<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
+fn item_macro_call_paren<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (Extent, Vec<TokenTree>)>
+{
     sequence!(pm, pt, {
         _    = left_paren;
-        args = parse_nested_until(Token::is_left_paren, Token::is_right_paren);
+        args = token_trees_until_close;
         _    = right_paren;
         _    = semicolon;
     }, |_, _| args)
 }
 
-fn item_macro_call_square<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
+fn item_macro_call_square<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (Extent, Vec<TokenTree>)>
+{
     sequence!(pm, pt, {
         _    = left_square;
-        args = parse_nested_until(Token::is_left_square, Token::is_right_square);
+        args = token_trees_until_close;
         _    = right_square;
         _    = semicolon;
     }, |_, _| args)
 }
 
-fn item_macro_call_curly<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
+fn item_macro_call_curly<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (Extent, Vec<TokenTree>)>
+{
     sequence!(pm, pt, {
         _    = left_curly;
-        args = parse_nested_until(Token::is_left_curly, Token::is_right_curly);
+        args = token_trees_until_close;
         _    = right_curly;
     }, |_, _| args)
 }
 
+fn macro_rules<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MacroRules> {
+    sequence!(pm, pt, {
+        spt   = point;
+        _     = kw_macro_rules;
+        _     = bang;
+        name  = ident;
+        rules = macro_rules_body;
+    }, |pm: &mut Master, pt| MacroRules {
+        extent: pm.state.ex(spt, pt),
+        name,
+        rules,
+        whitespace: Vec::new(),
+    })
+}
+
+fn macro_rules_body<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Vec<MacroRule>> {
+    pm.alternate(pt)
+        .one(macro_rules_body_curly)
+        .one(macro_rules_body_paren)
+        .one(macro_rules_body_square)
+        .finish()
+}
+
+fn macro_rules_body_curly<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Vec<MacroRule>> {
+    sequence!(pm, pt, {
+        _     = left_curly;
+        rules = zero_or_more_tailed_values(semicolon, macro_rule);
+        _     = macro_rules_end(right_curly);
+    }, |_, _| rules)
+}
+
+fn macro_rules_body_paren<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Vec<MacroRule>> {
+    sequence!(pm, pt, {
+        _     = left_paren;
+        rules = zero_or_more_tailed_values(semicolon, macro_rule);
+        _     = macro_rules_end(right_paren);
+        _     = semicolon;
+    }, |_, _| rules)
+}
+
+fn macro_rules_body_square<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Vec<MacroRule>> {
+    sequence!(pm, pt, {
+        _     = left_square;
+        rules = zero_or_more_tailed_values(semicolon, macro_rule);
+        _     = macro_rules_end(right_square);
+        _     = semicolon;
+    }, |_, _| rules)
+}
+
+// Once the rules have started, anything else is an error instead of
+// an opaque macro call
+fn macro_rules_end<'s, F>(f: F) -> impl Fn(&mut Master<'s>, Point<'s>) -> Progress<'s, Extent>
+    where F: Fn(&mut Master<'s>, Point<'s>) -> Progress<'s, Extent>
+{
+    move |pm, pt| f(pm, pt).map_err(|_| Error::ExpectedMacroRule)
+}
+
+fn macro_rule<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MacroRule> {
+    sequence!(pm, pt, {
+        spt         = point;
+        matcher     = macro_matcher;
+        _           = thick_arrow;
+        transcriber = token_tree_group;
+    }, |pm: &mut Master, pt| MacroRule {
+        extent: pm.state.ex(spt, pt),
+        matcher,
+        transcriber,
+        whitespace: Vec::new(),
+    })
+}
+
+fn macro_matcher<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MacroMatcher> {
+    pm.alternate(pt)
+        .one(macro_matcher_paren)
+        .one(macro_matcher_square)
+        .one(macro_matcher_curly)
+        .finish()
+}
+
+fn macro_matcher_paren<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MacroMatcher> {
+    sequence!(pm, pt, {
+        spt     = point;
+        _       = left_paren;
+        matches = zero_or_more(macro_match);
+        _       = right_paren;
+    }, |pm: &mut Master, pt| MacroMatcher {
+        extent: pm.state.ex(spt, pt),
+        delimiter: Delimiter::Paren,
+        matches,
+        whitespace: Vec::new(),
+    })
+}
+
+fn macro_matcher_square<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MacroMatcher> {
+    sequence!(pm, pt, {
+        spt     = point;
+        _       = left_square;
+        matches = zero_or_more(macro_match);
+        _       = right_square;
+    }, |pm: &mut Master, pt| MacroMatcher {
+        extent: pm.state.ex(spt, pt),
+        delimiter: Delimiter::Square,
+        matches,
+        whitespace: Vec::new(),
+    })
+}
+
+fn macro_matcher_curly<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MacroMatcher> {
+    sequence!(pm, pt, {
+        spt     = point;
+        _       = left_curly;
+        matches = zero_or_more(macro_match);
+        _       = right_curly;
+    }, |pm: &mut Master, pt| MacroMatcher {
+        extent: pm.state.ex(spt, pt),
+        delimiter: Delimiter::Curly,
+        matches,
+        whitespace: Vec::new(),
+    })
+}
+
+fn macro_match<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MacroMatch> {
+    pm.alternate(pt)
+        .one(map(macro_fragment, MacroMatch::Fragment))
+        .one(map(macro_repetition, MacroMatch::Repetition))
+        .one(map(macro_matcher, MacroMatch::Group))
+        .one(map(token_tree_leaf, MacroMatch::Token))
+        .finish()
+}
+
+fn macro_fragment<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MacroFragment> {
+    sequence!(pm, pt, {
+        spt       = point;
+        _         = dollar;
+        name      = macro_fragment_name;
+        _         = colon;
+        specifier = macro_fragment_specifier;
+    }, |pm: &mut Master, pt| MacroFragment {
+        extent: pm.state.ex(spt, pt),
+        name,
+        specifier,
+        whitespace: Vec::new(),
+    })
+}
+
+// Metavariables may be named by keywords, such as `$type`
+fn macro_fragment_name<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Ident> {
+    pm.alternate(pt)
+        .one(ident)
+        .one(map(token(Token::into_keyword, Error::ExpectedIdent), Ident::from))
+        .finish()
+}
+
+const FRAGMENT_SPECIFIERS: &[&str] = &[
+    "block", "expr", "expr_2021", "ident", "item", "lifetime", "literal",
+    "meta", "pat", "pat_param", "path", "stmt", "tt", "ty", "vis",
+];
+
+// The parser only sees tokens, so the name of the specifier has to be
+// checked against the source text.
+fn macro_fragment_specifier<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Ident> {
+    match ident(pm, pt) {
+        peresil::Progress { status: peresil::Status::Success(specifier), point }
+            if FRAGMENT_SPECIFIERS.contains(&&pm.state.text[specifier.name]) =>
+        {
+            Progress::success(point, specifier)
+        }
+        _ => {
+            pm.state.record_fatal_failure(pt);
+            Progress::failure(pt, Error::InvalidFragmentSpecifier)
+        }
+    }
+}
+
+fn macro_repetition<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MacroRepetition> {
+    sequence!(pm, pt, {
+        spt                   = point;
+        _                     = dollar;
+        _                     = left_paren;
+        matches               = zero_or_more(macro_match);
+        _                     = right_paren;
+        (separator, operator) = macro_repetition_tail;
+    }, |pm: &mut Master, pt| MacroRepetition {
+        extent: pm.state.ex(spt, pt),
+        matches,
+        separator,
+        operator,
+        whitespace: Vec::new(),
+    })
+}
+
+fn macro_repetition_tail<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (Option<TokenTreeLeaf>, MacroRepetitionOperator)>
+{
+    pm.alternate(pt)
+        .one(map(macro_repetition_operator, |op| (None, op)))
+        .one(macro_repetition_separator_and_operator)
+        .finish()
+        .map_err(|_| Error::ExpectedMacroRepetitionOperator)
+}
+
+fn macro_repetition_separator_and_operator<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (Option<TokenTreeLeaf>, MacroRepetitionOperator)>
+{
+    sequence!(pm, pt, {
+        separator = token_tree_leaf;
+        operator  = macro_repetition_operator;
+    }, |_, _| (Some(separator), operator))
+}
+
+fn macro_repetition_operator<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, MacroRepetitionOperator>
+{
+    pm.alternate(pt)
+        .one(map(asterisk, |_| MacroRepetitionOperator::ZeroOrMore))
+        .one(map(plus, |_| MacroRepetitionOperator::OneOrMore))
+        .one(map(question_mark, |_| MacroRepetitionOperator::ZeroOrOne))
+        .finish()
+}
+
+fn token_tree_group<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, TokenTreeGroup> {
+    pm.alternate(pt)
+        .one(token_tree_group_paren)
+        .one(token_tree_group_square)
+        .one(token_tree_group_curly)
+        .finish()
+}
+
+fn token_tree_group_paren<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, TokenTreeGroup> {
+    sequence!(pm, pt, {
+        spt        = point;
+        _          = left_paren;
+        (_, trees) = token_trees_until_close;
+        _          = right_paren;
+    }, |pm: &mut Master, pt| TokenTreeGroup {
+        extent: pm.state.ex(spt, pt),
+        delimiter: Delimiter::Paren,
+        trees,
+    })
+}
+
+fn token_tree_group_square<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, TokenTreeGroup> {
+    sequence!(pm, pt, {
+        spt        = point;
+        _          = left_square;
+        (_, trees) = token_trees_until_close;
+        _          = right_square;
+    }, |pm: &mut Master, pt| TokenTreeGroup {
+        extent: pm.state.ex(spt, pt),
+        delimiter: Delimiter::Square,
+        trees,
+    })
+}
+
+fn token_tree_group_curly<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, TokenTreeGroup> {
+    sequence!(pm, pt, {
+        spt        = point;
+        _          = left_curly;
+        (_, trees) = token_trees_until_close;
+        _          = right_curly;
+    }, |pm: &mut Master, pt| TokenTreeGroup {
+        extent: pm.state.ex(spt, pt),
+        delimiter: Delimiter::Curly,
+        trees,
+    })
+}
+
 fn character_literal<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Character> {
     character(pm, pt)
         .map(|extent| Character { extent, value: extent }) // FIXME: value
@@ -1083,36 +1992,76 @@ fn pathed_ident<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, PathedId
     })
 }
 
-fn path_component<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, PathComponent> {
-    sequence!(pm, pt, {
-        spt       = point;
-        ident     = path_member;
-        turbofish = optional(turbofish);
-    }, |pm: &mut Master, pt| PathComponent {
-        extent: pm.state.ex(spt, pt),
-        ident,
-        turbofish,
-        whitespace: Vec::new(),
+fn path_component<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, PathComponent> {
+    sequence!(pm, pt, {
+        spt       = point;
+        ident     = path_member;
+        turbofish = optional(turbofish);
+    }, |pm: &mut Master, pt| PathComponent {
+        extent: pm.state.ex(spt, pt),
+        ident,
+        turbofish,
+        whitespace: Vec::new(),
+    })
+}
+
+fn turbofish<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Turbofish> {
+    sequence!(pm, pt, {
+        spt     = point;
+        _       = double_colon;
+        _       = left_angle;
+        members = zero_or_more_tailed_values(comma, turbofish_member);
+        _       = right_angle;
+    }, |pm: &mut Master, pt| Turbofish {
+        extent: pm.state.ex(spt, pt),
+        members,
+        whitespace: Vec::new(),
+    })
+}
+
+// Unlike in a type, associated type constraints are not allowed
+fn turbofish_member<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, TypeGenericsAngleMember> {
+    pm.alternate(pt)
+        .one(map(lifetime, TypeGenericsAngleMember::Lifetime))
+        .one(map(typ, TypeGenericsAngleMember::Type))
+        .one(map(generic_argument_const, TypeGenericsAngleMember::Const))
+        .finish()
+}
+
+pub(crate) fn pattern<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Pattern> {
+    sequence!(pm, pt, {
+        spt     = point;
+        leading = optional(pipe);
+        first   = pattern_single;
+        rest    = zero_or_more(pattern_alternation_member);
+    }, move |pm: &mut Master, pt| {
+        if leading.is_none() && rest.is_empty() {
+            return first;
+        }
+
+        let extent = pm.state.ex(spt, pt);
+        let mut patterns = vec![first];
+        patterns.extend(rest);
+
+        Pattern {
+            extent,
+            name: None,
+            kind: PatternKind::Alternation(PatternAlternation { extent, patterns, whitespace: Vec::new() }),
+            whitespace: Vec::new(),
+        }
     })
 }
 
-fn turbofish<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Turbofish> {
+fn pattern_alternation_member<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Pattern> {
     sequence!(pm, pt, {
-        spt       = point;
-        _         = double_colon;
-        _         = left_angle;
-        lifetimes = zero_or_more_tailed_values(comma, lifetime);
-        types     = zero_or_more_tailed_values(comma, typ);
-        _     = right_angle;
-    }, |pm: &mut Master, pt| Turbofish {
-        extent: pm.state.ex(spt, pt),
-        lifetimes,
-        types,
-        whitespace: Vec::new(),
-    })
+        _       = pipe;
+        pattern = pattern_single;
+    }, |_, _| pattern)
 }
 
-fn pattern<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Pattern> {
+// A pattern without any top-level alternation, for places where a
+// `|` would be ambiguous, such as closure arguments.
+fn pattern_single<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Pattern> {
     sequence!(pm, pt, {
         spt  = point;
         name = optional(pattern_name);
@@ -1145,12 +2094,14 @@ fn pattern_kind<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, PatternK
         .one(map(pattern_number, PatternKind::Number))
         .one(map(pattern_reference, PatternKind::Reference))
         .one(map(pattern_byte_string, PatternKind::ByteString))
+        .one(map(pattern_c_string, PatternKind::CString))
         .one(map(pattern_string, PatternKind::String))
         .one(map(pattern_struct, PatternKind::Struct))
-        .one(map(pattern_tuple, PatternKind::Tuple))
+        .one(pattern_tuple_or_parenthesized)
         .one(map(pattern_slice, PatternKind::Slice))
         .one(map(pattern_macro_call, PatternKind::MacroCall))
         .one(map(pattern_box, PatternKind::Box))
+        .one(map(pattern_const_block, PatternKind::ConstBlock))
         // Must be last, otherwise it collides with struct names
         .one(map(pattern_ident, PatternKind::Ident))
         .finish()
@@ -1186,6 +2137,35 @@ fn pattern_tuple<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Pattern
     })
 }
 
+fn pattern_tuple_or_parenthesized<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, PatternKind> {
+    sequence!(pm, pt, {
+        spt     = point;
+        _       = left_paren;
+        members = zero_or_more_tailed(comma, pattern_tuple_member);
+        _       = right_paren;
+    }, move |pm: &mut Master, pt| {
+        let extent = pm.state.ex(spt, pt);
+        let Tailed { mut values, separator_count, .. } = members;
+        let is_parenthesized = separator_count == 0 && values.len() == 1 && values[0].is_pattern();
+
+        if is_parenthesized {
+            let pattern = values.pop().and_then(PatternTupleMember::into_pattern)
+                .expect("Must have one parenthesized pattern");
+            PatternKind::Parenthesized(PatternParenthesized {
+                extent,
+                pattern: Box::new(pattern),
+                whitespace: Vec::new(),
+            })
+        } else {
+            PatternKind::Tuple(PatternTuple {
+                extent,
+                members: values,
+                whitespace: Vec::new(),
+            })
+        }
+    })
+}
+
 fn pattern_tuple_member<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
     Progress<'s, PatternTupleMember>
 {
@@ -1224,12 +2204,14 @@ fn pattern_slice_subslice<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s
         is_ref = optional(kw_ref);
         is_mut = optional(kw_mut);
         name   = ident;
+        at     = optional(ext(at));
         _      = double_period;
     }, |pm: &mut Master, pt| PatternSliceSubslice {
         extent: pm.state.ex(spt, pt),
         is_ref,
         is_mut,
         name,
+        at,
         whitespace: Vec::new(),
     })
 }
@@ -1283,6 +2265,14 @@ fn pattern_byte_string<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, P
     expr_byte_string(pm, pt).map(|value| PatternByteString { extent: value.extent, value })
 }
 
+fn pattern_c_string<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, PatternCString> {
+    expr_c_string(pm, pt).map(|value| PatternCString { extent: value.extent, value })
+}
+
+fn pattern_const_block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, PatternConstBlock> {
+    expr_const_block(pm, pt).map(|value| PatternConstBlock { extent: value.extent, value })
+}
+
 fn pattern_string<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, PatternString> {
     string_literal(pm, pt).map(|value| PatternString { extent: value.extent, value })
 }
@@ -1305,7 +2295,7 @@ fn pattern_reference<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Pat
         spt     = point;
         _       = ampersand;
         is_mut  = optional(ext(kw_mut));
-        pattern = pattern;
+        pattern = pattern_single;
     }, |pm: &mut Master, pt| PatternReference {
         extent: pm.state.ex(spt, pt),
         is_mut,
@@ -1318,7 +2308,7 @@ fn pattern_box<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, PatternBo
     sequence!(pm, pt, {
         spt     = point;
         _       = kw_box;
-        pattern = pattern;
+        pattern = pattern_single;
     }, |pm: &mut Master, pt| PatternBox {
         extent: pm.state.ex(spt, pt),
         pattern: Box::new(pattern),
@@ -1328,20 +2318,44 @@ fn pattern_box<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, PatternBo
 
 fn pattern_range_exclusive<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
     Progress<'s, PatternRangeExclusive>
+{
+    pm.alternate(pt)
+        .one(pattern_range_exclusive_from)
+        .one(pattern_range_exclusive_to)
+        .finish()
+}
+
+fn pattern_range_exclusive_from<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, PatternRangeExclusive>
 {
     sequence!(pm, pt, {
         spt   = point;
         start = pattern_range_component;
         _     = double_period;
-        end   = pattern_range_component;
+        end   = optional(pattern_range_component);
     }, |pm: &mut Master, pt| PatternRangeExclusive {
         extent: pm.state.ex(spt, pt),
-        start,
+        start: Some(start),
         end,
         whitespace: Vec::new()
     })
 }
 
+fn pattern_range_exclusive_to<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, PatternRangeExclusive>
+{
+    sequence!(pm, pt, {
+        spt = point;
+        _   = double_period;
+        end = pattern_range_component;
+    }, |pm: &mut Master, pt| PatternRangeExclusive {
+        extent: pm.state.ex(spt, pt),
+        start: None,
+        end: Some(end),
+        whitespace: Vec::new()
+    })
+}
+
 fn range_inclusive_operator<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
     Progress<'s, RangeInclusiveOperator>
 {
@@ -1356,7 +2370,7 @@ fn pattern_range_inclusive<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
 {
     sequence!(pm, pt, {
         spt      = point;
-        start    = pattern_range_component;
+        start    = optional(pattern_range_component);
         operator = range_inclusive_operator;
         end      = pattern_range_component;
     }, |pm: &mut Master, pt| PatternRangeInclusive {
@@ -1371,6 +2385,7 @@ fn pattern_range_inclusive<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
 fn pattern_range_component<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, PatternRangeComponent> {
     pm.alternate(pt)
         .one(map(pathed_ident, PatternRangeComponent::Ident))
+        .one(map(expr_disambiguation, |d| PatternRangeComponent::Disambiguation(Box::new(d))))
         .one(map(character_literal, PatternRangeComponent::Character))
         .one(map(expr_byte, PatternRangeComponent::Byte))
         .one(map(pattern_number, PatternRangeComponent::Number))
@@ -1392,7 +2407,7 @@ fn p_struct<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Struct> {
         _              = kw_struct;
         name           = ident;
         generics       = optional(generic_declarations);
-        (body, wheres) = struct_defn_body;
+        (body, wheres) = named_context(Production::StructFields, name.extent, struct_defn_body);
     }, |pm: &mut Master, pt| Struct {
         extent: pm.state.ex(spt, pt),
         visibility,
@@ -1513,7 +2528,7 @@ fn p_enum<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Enum> {
         generics   = optional(generic_declarations);
         wheres     = optional(where_clause);
         _          = left_curly;
-        variants   = zero_or_more_tailed_values(comma, attributed(enum_variant));
+        variants   = named_context(Production::EnumVariants, name.extent, zero_or_more_tailed_values(comma, attributed(enum_variant)));
         _          = right_curly;
     }, |pm: &mut Master, pt| Enum {
         extent: pm.state.ex(spt, pt),
@@ -1566,7 +2581,8 @@ fn p_trait<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Trait> {
         bounds     = optional(generic_declaration_type_bounds);
         wheres     = optional(where_clause);
         _          = left_curly;
-        members    = zero_or_more(attributed(trait_impl_member));
+        attributes = zero_or_more(attribute_containing);
+        members    = named_context(Production::TraitBody, name.extent, zero_or_more(attributed(trait_impl_member)));
         _          = right_curly;
     }, |pm: &mut Master, pt| Trait {
         extent: pm.state.ex(spt, pt),
@@ -1577,11 +2593,34 @@ fn p_trait<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Trait> {
         generics,
         bounds,
         wheres: wheres.unwrap_or_else(Vec::new),
+        attributes,
         members,
         whitespace: Vec::new(),
     })
 }
 
+fn trait_alias<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, TraitAlias> {
+    sequence!(pm, pt, {
+        spt        = point;
+        visibility = optional(visibility);
+        _          = kw_trait;
+        name       = ident;
+        generics   = optional(generic_declarations);
+        _          = equals;
+        bounds     = trait_bounds;
+        wheres     = optional(where_clause);
+        _          = semicolon;
+    }, |pm: &mut Master, pt| TraitAlias {
+        extent: pm.state.ex(spt, pt),
+        visibility,
+        name,
+        generics,
+        bounds,
+        wheres: wheres.unwrap_or_default(),
+        whitespace: Vec::new(),
+    })
+}
+
 // TOOD: this is a terrrrrrible name. It is not an impl!
 fn trait_impl_member<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, TraitMember> {
     pm.alternate(pt)
@@ -1607,16 +2646,22 @@ fn trait_member_function<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s,
 
 fn trait_member_type<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, TraitMemberType> {
     sequence!(pm, pt, {
-        spt     = point;
-        _       = kw_type;
-        name    = ident;
-        bounds  = optional(generic_declaration_type_bounds);
-        default = optional(generic_declaration_type_default);
-        _       = semicolon;
+        spt          = point;
+        _            = kw_type;
+        name         = ident;
+        generics     = optional(generic_declarations);
+        bounds       = optional(generic_declaration_type_bounds);
+        wheres       = optional(where_clause);
+        default      = optional(generic_declaration_type_default);
+        wheres_after = optional(where_clause);
+        _            = semicolon;
     }, |pm: &mut Master, pt| TraitMemberType {
         extent: pm.state.ex(spt, pt),
         name,
+        generics,
         bounds,
+        wheres: wheres.unwrap_or_default(),
+        wheres_after: wheres_after.unwrap_or_default(),
         default,
         whitespace: Vec::new(),
     })
@@ -1728,7 +2773,7 @@ fn trait_impl_function_argument_name<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
     Progress<'s, Pattern>
 {
     sequence!(pm, pt, {
-        name = pattern;
+        name = pattern_single;
         _    = colon;
     }, |_, _| name)
 }
@@ -1742,21 +2787,23 @@ fn trait_impl_function_body<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<
 
 fn p_impl<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Impl> {
     sequence!(pm, pt, {
-        spt       = point;
-        is_unsafe = optional(ext(kw_unsafe));
-        _         = kw_impl;
-        generics  = optional(generic_declarations);
-        kind      = p_impl_kind;
-        wheres    = optional(where_clause);
-        _         = left_curly;
-        body      = zero_or_more(attributed(impl_member));
-        _         = right_curly;
+        spt        = point;
+        is_unsafe  = optional(ext(kw_unsafe));
+        _          = kw_impl;
+        generics   = optional(generic_declarations);
+        kind       = p_impl_kind;
+        wheres     = optional(where_clause);
+        _          = left_curly;
+        attributes = zero_or_more(attribute_containing);
+        body       = named_context(Production::ImplBody, kind.extent(), zero_or_more(attributed(impl_member)));
+        _          = right_curly;
     }, |pm: &mut Master, pt| Impl {
         extent: pm.state.ex(spt, pt),
         is_unsafe,
         generics,
         kind,
         wheres: wheres.unwrap_or_else(Vec::new),
+        attributes,
         body,
         whitespace: Vec::new(),
     })
@@ -1827,13 +2874,24 @@ fn impl_function<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ImplFun
 
 fn impl_type<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ImplType> {
     sequence!(pm, pt, {
-        spt  = point;
-        _    = kw_type;
-        name = ident;
-        _    = equals;
-        typ  = typ;
-        _    = semicolon;
-    }, |pm: &mut Master, pt| ImplType { extent: pm.state.ex(spt, pt), name, typ, whitespace: Vec::new() })
+        spt          = point;
+        _            = kw_type;
+        name         = ident;
+        generics     = optional(generic_declarations);
+        wheres       = optional(where_clause);
+        _            = equals;
+        typ          = typ;
+        wheres_after = optional(where_clause);
+        _            = semicolon;
+    }, |pm: &mut Master, pt| ImplType {
+        extent: pm.state.ex(spt, pt),
+        name,
+        generics,
+        typ,
+        wheres: wheres.unwrap_or_default(),
+        wheres_after: wheres_after.unwrap_or_default(),
+        whitespace: Vec::new(),
+    })
 }
 
 fn impl_const<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ImplConst> {
@@ -1908,13 +2966,22 @@ fn extern_crate_rename<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, I
 
 fn extern_block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ExternBlock> {
     sequence!(pm, pt, {
-        spt     = point;
-        _       = kw_extern;
-        abi     = optional(string_literal);
-        _       = left_curly;
-        members = zero_or_more(attributed(extern_block_member));
-        _       = right_curly;
-    }, |pm: &mut Master, pt| ExternBlock { extent: pm.state.ex(spt, pt), abi, members, whitespace: Vec::new() })
+        spt        = point;
+        is_unsafe  = optional(ext(kw_unsafe));
+        _          = kw_extern;
+        abi        = optional(string_literal);
+        _          = left_curly;
+        attributes = zero_or_more(attribute_containing);
+        members    = zero_or_more(attributed(extern_block_member));
+        _          = right_curly;
+    }, |pm: &mut Master, pt| ExternBlock {
+        extent: pm.state.ex(spt, pt),
+        is_unsafe,
+        abi,
+        attributes,
+        members,
+        whitespace: Vec::new(),
+    })
 }
 
 fn extern_block_member<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ExternBlockMember> {
@@ -1925,18 +2992,34 @@ fn extern_block_member<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, E
         .finish()
 }
 
+fn extern_safety<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ExternSafety> {
+    pm.alternate(pt)
+        .one(map(kw_safe, ExternSafety::Safe))
+        .one(map(ext(kw_unsafe), ExternSafety::Unsafe))
+        .finish()
+}
+
 // TODO: very similar to regular statics; DRY
 fn extern_block_static<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ExternBlockMemberStatic> {
     sequence!(pm, pt, {
         spt        = point;
         visibility = optional(visibility);
+        safety     = optional(extern_safety);
         _          = kw_static;
         is_mut     = optional(ext(kw_mut));
         name       = ident;
         _          = colon;
         typ        = typ;
         _          = semicolon;
-    }, |pm: &mut Master, pt| ExternBlockMemberStatic { extent: pm.state.ex(spt, pt), visibility, is_mut, name, typ, whitespace: Vec::new() })
+    }, |pm: &mut Master, pt| ExternBlockMemberStatic {
+        extent: pm.state.ex(spt, pt),
+        visibility,
+        safety,
+        is_mut,
+        name,
+        typ,
+        whitespace: Vec::new(),
+    })
 }
 
 fn extern_block_type<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ExternBlockMemberType> {
@@ -1961,6 +3044,7 @@ fn extern_block_member_function<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
     sequence!(pm, pt, {
         spt         = point;
         visibility  = optional(visibility);
+        safety      = optional(extern_safety);
         _           = kw_fn;
         name        = ident;
         generics    = optional(generic_declarations);
@@ -1972,6 +3056,7 @@ fn extern_block_member_function<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
         ExternBlockMemberFunction {
             extent: pm.state.ex(spt, pt),
             visibility,
+            safety,
             name,
             generics,
             arguments,
@@ -2006,7 +3091,7 @@ fn extern_block_function_argument_named<'s>(pm: &mut Master<'s>, pt: Point<'s>)
 {
     sequence!(pm, pt, {
         spt  = point;
-        name = pattern;
+        name = pattern_single;
         _    = colon;
         typ  = typ;
     }, |pm: &mut Master, pt| ExternBlockMemberFunctionArgumentNamed {
@@ -2061,7 +3146,7 @@ fn use_path_component<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Id
 
 fn path_member<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Ident> {
     pm.alternate(pt)
-        .one(map(kw_crate, |extent| Ident { extent }))
+        .one(map(kw_crate, Ident::from))
         .one(ident)
         .finish()
 }
@@ -2138,30 +3223,42 @@ fn type_alias<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, TypeAlias>
 
 fn module<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Module> {
     sequence!(pm, pt, {
-        spt        = point;
-        visibility = optional(visibility);
-        _          = kw_mod;
-        name       = ident;
-        body       = module_body_or_not;
-    }, |pm: &mut Master, pt| Module { extent: pm.state.ex(spt, pt), visibility, name, body, whitespace: Vec::new() })
+        spt                = point;
+        visibility         = optional(visibility);
+        _                  = kw_mod;
+        name               = ident;
+        (attributes, body) = named_context(Production::ModuleBody, name.extent, module_body_or_not);
+    }, |pm: &mut Master, pt| Module {
+        extent: pm.state.ex(spt, pt),
+        visibility,
+        name,
+        attributes,
+        body,
+        whitespace: Vec::new(),
+    })
 }
 
-fn module_body_or_not<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Option<Vec<Attributed<Item>>>> {
+type ModuleBody = (Vec<AttributeContaining>, Option<Vec<Attributed<Item>>>);
+
+fn module_body_or_not<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ModuleBody> {
     pm.alternate(pt)
-        .one(map(module_body, Some))
-        .one(map(semicolon, |_| None))
+        .one(map(module_body, |(attributes, body)| (attributes, Some(body))))
+        .one(map(semicolon, |_| (Vec::new(), None)))
         .finish()
 }
 
-fn module_body<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Vec<Attributed<Item>>> {
+fn module_body<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, (Vec<AttributeContaining>, Vec<Attributed<Item>>)>
+{
     sequence!(pm, pt, {
-        _    = left_curly;
-        body = zero_or_more(attributed(item));
-        _    = right_curly;
-    }, |_, _| body)
+        _          = left_curly;
+        attributes = zero_or_more(attribute_containing);
+        body       = zero_or_more(attributed(item));
+        _          = right_curly;
+    }, |_, _| (attributes, body))
 }
 
-fn typ<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Type> {
+pub(crate) fn typ<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Type> {
     sequence!(pm, pt, {
         spt        = point;
         kind       = typ_kind;
@@ -2438,8 +3535,11 @@ fn typ_generics_angle_member<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
 {
     pm.alternate(pt)
         .one(map(associated_type, TypeGenericsAngleMember::AssociatedType))
+        .one(map(associated_type_bound, TypeGenericsAngleMember::AssociatedTypeBound))
+        .one(map(generic_associated_type, TypeGenericsAngleMember::GenericAssociatedType))
         .one(map(lifetime, TypeGenericsAngleMember::Lifetime))
         .one(map(typ, TypeGenericsAngleMember::Type))
+        .one(map(generic_argument_const, TypeGenericsAngleMember::Const))
         .finish()
 }
 
@@ -2452,6 +3552,35 @@ fn associated_type<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Assoc
     }, |pm: &mut Master, pt| AssociatedType { extent: pm.state.ex(spt, pt), name, value, whitespace: Vec::new() })
 }
 
+fn associated_type_bound<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, AssociatedTypeBound> {
+    sequence!(pm, pt, {
+        spt    = point;
+        name   = ident;
+        bounds = generic_declaration_type_bounds;
+    }, |pm: &mut Master, pt| AssociatedTypeBound {
+        extent: pm.state.ex(spt, pt),
+        name,
+        bounds,
+        whitespace: Vec::new(),
+    })
+}
+
+fn generic_associated_type<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, GenericAssociatedType> {
+    sequence!(pm, pt, {
+        spt      = point;
+        name     = ident;
+        generics = typ_generics_angle;
+        _        = equals;
+        value    = typ;
+    }, |pm: &mut Master, pt| GenericAssociatedType {
+        extent: pm.state.ex(spt, pt),
+        name,
+        generics,
+        value,
+        whitespace: Vec::new(),
+    })
+}
+
 fn typ_function<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, TypeFunction> {
     sequence!(pm, pt, {
         spt         = point;
@@ -2503,7 +3632,7 @@ fn typ_function_argument_variadic<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
 
 fn lifetime<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Lifetime> {
     lifetime_normal(pm, pt)
-        .map(|extent| Lifetime { extent: extent, name: Ident { extent } })
+        .map(|l| Lifetime { extent: l.extent, name: Ident { extent: l.extent, name: l.name, is_raw: l.is_raw } })
     // FIXME: value; can we actually have whitespace here?
 }
 
@@ -2536,18 +3665,27 @@ fn attribute<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Attribute>
 
 fn attribute_literal<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, AttributeLiteral> {
     sequence!(pm, pt, {
-        spt  = point;
-        _    = hash;
-        _    = left_square;
-        text = parse_nested_until(Token::is_left_square, Token::is_right_square);
-        _    = right_square;
+        spt                     = point;
+        _                       = hash;
+        _                       = left_square;
+        (is_unsafe, meta, text) = context(Production::Attribute, attribute_contents);
+        _                       = right_square;
     }, |pm: &mut Master, pt| AttributeLiteral {
         extent: pm.state.ex(spt, pt),
+        is_unsafe,
+        meta,
         text,
         whitespace: Vec::new(),
     })
 }
 
+// The inner attributes at the top of a file
+pub(crate) fn file_attributes<'s>(pm: &mut Master<'s>, pt: Point<'s>) ->
+    Progress<'s, Vec<AttributeContaining>>
+{
+    zero_or_more(attribute_containing)(pm, pt)
+}
+
 fn attribute_containing<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, AttributeContaining> {
     pm.alternate(pt)
         .one(map(doc_comment_inner_line, AttributeContaining::DocCommentLine))
@@ -2558,19 +3696,113 @@ fn attribute_containing<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s,
 
 fn attribute_containing_literal<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, AttributeContainingLiteral> {
     sequence!(pm, pt, {
-        spt  = point;
-        _    = hash;
-        _    = bang;
-        _    = left_square;
-        text = parse_nested_until(Token::is_left_square, Token::is_right_square);
-        _    = right_square;
+        spt                     = point;
+        _                       = hash;
+        _                       = bang;
+        _                       = left_square;
+        (is_unsafe, meta, text) = context(Production::Attribute, attribute_contents);
+        _                       = right_square;
     }, |pm: &mut Master, pt| AttributeContainingLiteral {
         extent: pm.state.ex(spt, pt),
+        is_unsafe,
+        meta,
         text,
         whitespace: Vec::new(),
     })
 }
 
+type AttributeContents = (Option<Extent>, Option<Meta>, Extent);
+
+fn attribute_contents<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, AttributeContents> {
+    pm.alternate(pt)
+        .one(attribute_contents_unsafe)
+        .one(map(attribute_meta(Token::is_left_square, Token::is_right_square), |(meta, text)| {
+            (None, meta, text)
+        }))
+        .finish()
+}
+
+fn attribute_contents_unsafe<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, AttributeContents> {
+    sequence!(pm, pt, {
+        is_unsafe    = ext(kw_unsafe);
+        _            = left_paren;
+        (meta, text) = attribute_meta(Token::is_left_paren, Token::is_right_paren);
+        _            = right_paren;
+        _            = peek(right_square);
+    }, |_, _| (Some(is_unsafe), meta, text))
+}
+
+// Attributes may contain arbitrary tokens, so the structured form is
+// only available when it covers the entire contents. Arbitrary tokens
+// are not a failure, so the structured form is attempted with a
+// separate master that does not keep its failures.
+fn attribute_meta<'s, O, C>(is_open: O, is_close: C) ->
+    impl Fn(&mut Master<'s>, Point<'s>) -> Progress<'s, (Option<Meta>, Extent)>
+    where O: Fn(&Token) -> bool,
+          C: Fn(&Token) -> bool,
+{
+    move |pm, pt| {
+        let mut probe = Master::with_state(std::mem::take(&mut pm.state));
+        let furthest_failure = probe.state.furthest_failure.take();
+        let meta = meta(&mut probe, pt);
+        pm.state = probe.state;
+        pm.state.furthest_failure = furthest_failure;
+
+        match meta {
+            peresil::Progress { status: peresil::Status::Success(meta), point }
+                if point.s.first().is_some_and(&is_close) =>
+            {
+                Progress::success(point, (Some(meta), pm.state.ex(pt, point)))
+            }
+            _ => parse_nested_until(&is_open, &is_close)(pm, pt).map(|text| (None, text)),
+        }
+    }
+}
+
+fn meta<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Meta> {
+    pm.alternate(pt)
+        .one(map(meta_list, Meta::List))
+        .one(map(meta_name_value, Meta::NameValue))
+        .one(map(pathed_ident, Meta::Path))
+        .finish()
+}
+
+fn meta_list<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MetaList> {
+    sequence!(pm, pt, {
+        spt     = point;
+        path    = pathed_ident;
+        _       = left_paren;
+        members = zero_or_more_tailed_values(comma, meta_list_member);
+        _       = right_paren;
+    }, |pm: &mut Master, pt| MetaList {
+        extent: pm.state.ex(spt, pt),
+        path,
+        members,
+        whitespace: Vec::new(),
+    })
+}
+
+fn meta_list_member<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MetaListMember> {
+    pm.alternate(pt)
+        .one(map(meta, MetaListMember::Meta))
+        .one(map(expr_literal, |e| MetaListMember::Literal(Box::new(e.into()))))
+        .finish()
+}
+
+fn meta_name_value<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, MetaNameValue> {
+    sequence!(pm, pt, {
+        spt   = point;
+        path  = pathed_ident;
+        _     = equals;
+        value = expression;
+    }, |pm: &mut Master, pt| MetaNameValue {
+        extent: pm.state.ex(spt, pt),
+        path,
+        value: Box::new(value),
+        whitespace: Vec::new(),
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -2649,39 +3881,135 @@ mod test {
     }
 
     #[test]
-    fn item_macro_call_with_parens() {
-        let p = qp(item, "foo!();");
-        assert_extent!(p, (0, 7))
+    fn item_macro_call_with_parens() {
+        let p = qp(item, "foo!();");
+        assert_extent!(p, (0, 7))
+    }
+
+    #[test]
+    fn item_macro_call_with_square_brackets() {
+        let p = qp(item, "foo![];");
+        assert_extent!(p, (0, 7))
+    }
+
+    #[test]
+    fn item_macro_call_with_curly_braces() {
+        let p = qp(item, "foo! { }");
+        assert_extent!(p, (0, 8))
+    }
+
+    #[test]
+    fn item_macro_rules_without_rules() {
+        let p = qp(item, "macro_rules! name { }");
+        assert_extent!(p, (0, 21));
+
+        let m = p.into_macro_rules().expect("Must be macro_rules");
+        assert_extent!(m.name, (13, 17));
+        assert!(m.rules.is_empty());
+    }
+
+    #[test]
+    fn item_macro_call_with_path() {
+        let p = qp(item, "foo::bar!();");
+        assert_extent!(p, (0, 12))
+    }
+
+    #[test]
+    fn item_macro_call_all_space() {
+        let p = qp(item, "foo :: bar ! baz [ ] ;");
+        assert_extent!(p, (0, 22))
+    }
+
+    #[test]
+    fn item_macro_rules() {
+        let p = qp(item, "macro_rules! a { ($b:expr) => { $b + 1 }; () => {} }");
+        assert_extent!(p, (0, 52));
+
+        let m = p.into_macro_rules().expect("Must be macro_rules");
+        assert_extent!(m.name, (13, 14));
+        assert_eq!(m.rules.len(), 2);
+
+        let rule = &m.rules[0];
+        assert_extent!(rule.matcher, (17, 26));
+        assert_eq!(rule.matcher.delimiter, Delimiter::Paren);
+        assert_extent!(rule.transcriber, (30, 40));
+        assert_eq!(rule.transcriber.trees.len(), 4);
+
+        let f = rule.matcher.matches[0].as_fragment().expect("Must be a fragment");
+        assert_extent!(f, (18, 25));
+        assert_extent!(f.name, (19, 20));
+        assert_extent!(f.specifier, (21, 25));
+    }
+
+    #[test]
+    fn item_macro_rules_with_parens() {
+        let p = qp(item, "macro_rules! a ( () => () );");
+        assert_extent!(p, (0, 28));
+        assert!(p.is_macro_rules());
+    }
+
+    #[test]
+    fn macro_matcher_repetition_with_separator() {
+        let p = qp(macro_matcher, "($($k:ident => $v:expr),* ; $(+)?)");
+        assert_extent!(p, (0, 34));
+        assert_eq!(p.matches.len(), 3);
+
+        let r = p.matches[0].as_repetition().expect("Must be a repetition");
+        assert_extent!(r, (1, 25));
+        assert_eq!(r.matches.len(), 3);
+        assert_extent!(r.separator.as_ref().expect("Must have a separator"), (23, 24));
+        assert_eq!(r.operator, MacroRepetitionOperator::ZeroOrMore);
+
+        let r = p.matches[2].as_repetition().expect("Must be a repetition");
+        assert!(r.separator.is_none());
+        assert_eq!(r.operator, MacroRepetitionOperator::ZeroOrOne);
+    }
+
+    #[test]
+    fn macro_matcher_nested_groups() {
+        let p = qp(macro_matcher, "{ [$a:tt] ($b:tt) }");
+        assert_eq!(p.delimiter, Delimiter::Curly);
+        assert!(p.matches[0].as_group().expect("Must be a group").matches[0].is_fragment());
+        assert!(p.matches[1].is_group());
     }
 
     #[test]
-    fn item_macro_call_with_square_brackets() {
-        let p = qp(item, "foo![];");
-        assert_extent!(p, (0, 7))
+    fn item_macro_rules_repetition_without_operator_fails() {
+        let r = parse_full(item, "macro_rules! a { ($($b:tt)) => {} }");
+        let (err_loc, errs) = unwrap_progress_err(r);
+        assert_eq!(err_loc, 12);
+        assert!(errs.contains(&Error::ExpectedMacroRepetitionOperator));
     }
 
     #[test]
-    fn item_macro_call_with_curly_braces() {
-        let p = qp(item, "foo! { }");
-        assert_extent!(p, (0, 8))
+    fn macro_matcher_keyword_fragment_name() {
+        let p = qp(macro_matcher, "($type:ty)");
+        let f = p.matches[0].as_fragment().expect("Must be a fragment");
+        assert_extent!(f.name, (2, 6));
+        assert_extent!(f.specifier, (7, 9));
     }
 
     #[test]
-    fn item_macro_call_with_ident() {
-        let p = qp(item, "macro_rules! name { }");
-        assert_extent!(p, (0, 21))
+    fn macro_matcher_raw_fragment_name() {
+        let p = qp(macro_matcher, "($r#match:expr)");
+        let f = p.matches[0].as_fragment().expect("Must be a fragment");
+        assert!(f.name.is_raw);
     }
 
     #[test]
-    fn item_macro_call_with_path() {
-        let p = qp(item, "foo::bar!();");
-        assert_extent!(p, (0, 12))
+    fn item_macro_rules_invalid_fragment_specifier_fails() {
+        let r = parse_full(item, "macro_rules! a { ($b:exp) => {} }");
+        let (err_loc, errs) = unwrap_progress_err(r);
+        assert_eq!(err_loc, 8);
+        assert!(errs.contains(&Error::InvalidFragmentSpecifier));
     }
 
     #[test]
-    fn item_macro_call_all_space() {
-        let p = qp(item, "foo :: bar ! baz [ ] ;");
-        assert_extent!(p, (0, 22))
+    fn item_macro_rules_malformed_rule_fails() {
+        let r = parse_full(item, "macro_rules! a { () {} }");
+        let (err_loc, errs) = unwrap_progress_err(r);
+        assert_eq!(err_loc, 6);
+        assert!(errs.contains(&Error::ExpectedMacroRule));
     }
 
     #[test]
@@ -2702,6 +4030,15 @@ mod test {
         assert_extent!(p, (0, 8))
     }
 
+    #[test]
+    fn item_mod_with_inner_attributes() {
+        let p = qp(module, "mod foo { //! Docs\n #![allow(unused)] fn a() {} }");
+        assert_extent!(p, (0, 49));
+        assert_eq!(p.attributes.len(), 2);
+        assert_extent!(p.attributes[1], (20, 37));
+        assert_eq!(p.body.unwrap().len(), 1)
+    }
+
     #[test]
     fn item_trait() {
         let p = qp(item, "trait Foo {}");
@@ -2714,6 +4051,34 @@ mod test {
         assert_extent!(p, (0, 17))
     }
 
+    #[test]
+    fn item_trait_alias() {
+        let p = qp(item, "trait Shape = Clone + Debug;");
+        assert_extent!(p, (0, 28));
+        let a = p.into_trait_alias().expect("Must be a trait alias");
+        assert_extent!(a.name, (6, 11));
+        assert_extent!(a.bounds, (14, 27));
+        assert_eq!(a.bounds.types.len(), 2);
+    }
+
+    #[test]
+    fn item_trait_alias_with_generics_and_where() {
+        let p = qp(item, "pub trait Sendable<T> = Send where T: Sync;");
+        assert_extent!(p, (0, 43));
+        let a = p.into_trait_alias().expect("Must be a trait alias");
+        assert!(a.visibility.is_some());
+        assert!(a.generics.is_some());
+        assert_eq!(a.wheres.len(), 1);
+    }
+
+    #[test]
+    fn item_trait_with_inner_attributes() {
+        let p = qp(p_trait, "trait Foo { #![allow(unused)] fn a(); }");
+        assert_extent!(p, (0, 39));
+        assert_eq!(p.attributes.len(), 1);
+        assert_eq!(p.members.len(), 1)
+    }
+
     #[test]
     fn item_trait_public() {
         let p = qp(item, "pub trait Foo {}");
@@ -2786,6 +4151,23 @@ mod test {
         assert_extent!(p, (0, 33))
     }
 
+    #[test]
+    fn item_trait_with_generic_associated_type() {
+        let p = qp(item, "trait Foo { type Bar<'a>: Baz where Self: 'a; }");
+        assert_extent!(p, (0, 47))
+    }
+
+    #[test]
+    fn trait_member_generic_associated_type_with_default() {
+        let p = qp(trait_member_type, "type Bar<'a, T> where T: 'a = &'a T where Self: 'a;");
+        assert_extent!(p, (0, 51));
+        assert_eq!(p.generics.expect("Must have generics").members.len(), 2);
+        assert_eq!(p.wheres.len(), 1);
+        assert_extent!(p.wheres[0], (22, 27));
+        assert_eq!(p.wheres_after.len(), 1);
+        assert_extent!(p.wheres_after[0], (42, 50));
+    }
+
     #[test]
     fn item_trait_with_associated_const() {
         let p = qp(item, "trait Foo { const Bar: u8; }");
@@ -2900,6 +4282,14 @@ mod test {
         assert_extent!(p, (0, 13))
     }
 
+    #[test]
+    fn item_extern_block_with_inner_attributes() {
+        let p = qp(extern_block, r#"extern "C" { #![allow(unused)] fn a(); }"#);
+        assert_extent!(p, (0, 40));
+        assert_eq!(p.attributes.len(), 1);
+        assert_eq!(p.members.len(), 1)
+    }
+
     #[test]
     fn item_extern_block_with_fn() {
         let p = qp(item, r#"extern { fn foo(bar: u8) -> bool; }"#);
@@ -2936,6 +4326,29 @@ mod test {
         assert_extent!(p, (0, 35))
     }
 
+    #[test]
+    fn item_extern_block_unsafe() {
+        let p = qp(extern_block, r#"unsafe extern "C" { safe fn f(); unsafe static X: i32; pub safe static Y: u8; }"#);
+        assert_extent!(p, (0, 79));
+        assert!(p.is_unsafe.is_some());
+
+        let mut members = p.members.into_iter().map(|m| m.value);
+        let f = members.next().unwrap().into_function().unwrap();
+        assert!(f.safety.unwrap().is_safe());
+        let x = members.next().unwrap().into_static().unwrap();
+        assert_extent!(x.safety.unwrap(), (33, 39));
+        let y = members.next().unwrap().into_static().unwrap();
+        assert!(y.visibility.is_some());
+        assert!(y.safety.unwrap().is_safe());
+    }
+
+    #[test]
+    fn item_extern_block_member_without_safety() {
+        let p = qp(extern_block, r#"extern "C" { fn f(); }"#);
+        let f = p.members.into_iter().next().unwrap().value.into_function().unwrap();
+        assert!(f.safety.is_none());
+    }
+
     #[test]
     fn item_extern_block_with_type() {
         let p = qp(item, r#"extern { type opaque; }"#);
@@ -2966,12 +4379,107 @@ mod test {
         assert_extent!(p, (0, 12))
     }
 
+    #[test]
+    fn attribute_meta_path() {
+        let p = qp(attribute_literal, "#[test]");
+        let meta = p.meta.unwrap();
+        let path = unwrap_as!(meta, Meta::Path);
+        assert_extent!(path, (2, 6))
+    }
+
+    #[test]
+    fn attribute_meta_list() {
+        let p = qp(attribute_literal, "#[derive(Debug, Clone)]");
+        let meta = p.meta.unwrap();
+        let list = unwrap_as!(meta, Meta::List);
+        assert_extent!(list, (2, 22));
+        assert_extent!(list.path, (2, 8));
+        assert_eq!(list.members.len(), 2);
+        assert_extent!(list.members[1], (16, 21))
+    }
+
+    #[test]
+    fn attribute_meta_nested() {
+        let p = qp(attribute_literal, r#"#[cfg(all(unix, feature = "x"))]"#);
+        let meta = p.meta.unwrap();
+        let list = unwrap_as!(meta, Meta::List);
+        assert_eq!(list.members.len(), 1);
+        let inner = list.members.into_iter().next().unwrap();
+        let inner = unwrap_as!(inner, MetaListMember::Meta);
+        let inner = unwrap_as!(inner, Meta::List);
+        assert_eq!(inner.members.len(), 2);
+        let name_value = inner.members.into_iter().nth(1).unwrap();
+        let name_value = unwrap_as!(name_value, MetaListMember::Meta);
+        let name_value = unwrap_as!(name_value, Meta::NameValue);
+        assert_extent!(name_value, (16, 29));
+        assert_extent!(name_value.value, (26, 29))
+    }
+
+    #[test]
+    fn attribute_meta_list_with_literals() {
+        let p = qp(attribute_literal, r#"#[rustc_layout_scalar_valid_range_start(1, "a")]"#);
+        let meta = p.meta.unwrap();
+        let list = unwrap_as!(meta, Meta::List);
+        assert_eq!(list.members.len(), 2);
+        assert!(list.members[0].is_literal());
+        assert!(list.members[1].is_literal())
+    }
+
+    #[test]
+    fn attribute_meta_name_value() {
+        let p = qp(attribute_literal, r#"#[doc = "Hello"]"#);
+        let meta = p.meta.unwrap();
+        let name_value = unwrap_as!(meta, Meta::NameValue);
+        assert_extent!(name_value.path, (2, 5));
+        assert_extent!(name_value.value, (8, 15))
+    }
+
+    #[test]
+    fn attribute_containing_meta() {
+        let p = qp(attribute_containing_literal, "#![feature(nll)]");
+        let meta = p.meta.unwrap();
+        assert!(meta.is_list())
+    }
+
+    #[test]
+    fn attribute_unsafe() {
+        let p = qp(attribute_literal, "#[unsafe(no_mangle)]");
+        assert_extent!(p, (0, 20));
+        assert_extent!(p.is_unsafe.unwrap(), (2, 8));
+        assert_extent!(p.text, (9, 18));
+        let meta = p.meta.unwrap();
+        let path = unwrap_as!(meta, Meta::Path);
+        assert_extent!(path, (9, 18))
+    }
+
+    #[test]
+    fn attribute_unsafe_with_name_value() {
+        let p = qp(attribute_literal, r#"#[unsafe(export_name = "f")]"#);
+        assert!(p.is_unsafe.is_some());
+        assert!(p.meta.unwrap().is_name_value())
+    }
+
+    #[test]
+    fn attribute_meta_falls_back_to_text() {
+        let p = qp(attribute_literal, "#[foo(a + b)]");
+        assert!(p.meta.is_none());
+        assert_extent!(p.text, (2, 12))
+    }
+
     #[test]
     fn inherent_impl() {
         let p = qp(p_impl, "impl Bar {}");
         assert_extent!(p, (0, 11))
     }
 
+    #[test]
+    fn inherent_impl_with_inner_attributes() {
+        let p = qp(p_impl, "impl Bar { #![allow(unused)] fn foo() {} }");
+        assert_extent!(p, (0, 42));
+        assert_eq!(p.attributes.len(), 1);
+        assert_eq!(p.body.len(), 1)
+    }
+
     #[test]
     fn inherent_impl_with_function() {
         let p = qp(p_impl, "impl Bar { fn foo() {} }");
@@ -3068,6 +4576,28 @@ mod test {
         assert_extent!(p, (0, 24))
     }
 
+    #[test]
+    fn impl_with_generic_associated_type() {
+        let p = qp(impl_type, "type A<'a> = &'a T where Self: 'a;");
+        assert_extent!(p, (0, 34));
+        assert!(p.generics.is_some());
+        assert!(p.wheres.is_empty());
+        assert_eq!(p.wheres_after.len(), 1);
+    }
+
+    #[test]
+    fn impl_with_generic_associated_type_leading_where() {
+        let p = qp(p_impl, "impl Foo { type A<'a> where Self: 'a = &'a T; }");
+        assert_extent!(p, (0, 47))
+    }
+
+    #[test]
+    fn impl_type_keeps_leading_where_separate() {
+        let p = qp(impl_type, "type A<'a> where Self: 'a = &'a T;");
+        assert_eq!(p.wheres.len(), 1);
+        assert!(p.wheres_after.is_empty());
+    }
+
     #[test]
     fn impl_with_associated_const() {
         let p = qp(p_impl, "impl Foo { const A: i32 = 42; }");
@@ -3254,6 +4784,12 @@ mod test {
         assert_extent!(p, (0, 15))
     }
 
+    #[test]
+    fn fn_with_arguments_with_parenthesized_alternation() {
+        let p = qp(function_header, "fn foo((A(a) | B(a)): E)");
+        assert_extent!(p, (0, 24))
+    }
+
     #[test]
     fn fn_with_return_type() {
         let p = qp(function_header, "fn foo() -> bool");
@@ -3315,6 +4851,16 @@ mod test {
         assert_extent!(p, (0, 9));
     }
 
+    #[test]
+    fn block_with_inner_attributes() {
+        let p = qp(block, "{ #![allow(unused)] /*! Docs */ let a = 1; }");
+        assert_extent!(p, (0, 44));
+        assert_eq!(p.attributes.len(), 2);
+        assert_extent!(p.attributes[0], (2, 19));
+        assert_extent!(p.attributes[1], (20, 31));
+        assert_eq!(p.statements.len(), 1)
+    }
+
     #[test]
     fn statement_match_no_semicolon() {
         let p = qp(statement, "match a { _ => () }");
@@ -3346,6 +4892,37 @@ mod test {
         assert_extent!(p, (0, 33))
     }
 
+    #[test]
+    fn statement_labeled_block_no_semicolon() {
+        let p = qp(block, "{ 'a: { break 'a; } 'b: {} }");
+        assert_extent!(p, (0, 28));
+        assert!(p.expression.expect("Must have an expression").is_labeled_block());
+    }
+
+    #[test]
+    fn statement_const_block_no_semicolon() {
+        let p = qp(block, "{ const { assert!(true) } const A: u8 = 1; const {} }");
+        assert_extent!(p, (0, 53));
+        assert_eq!(p.statements.len(), 2);
+        assert!(p.statements[0].is_expression());
+        assert!(p.statements[1].is_item());
+        assert!(p.expression.expect("Must have an expression").is_const_block());
+    }
+
+    #[test]
+    fn statement_let_else() {
+        let p = qp(block, "{ let Some(x) = y else { return; }; }");
+        assert_extent!(p, (0, 37));
+        assert_eq!(p.statements.len(), 1);
+        assert!(p.statements[0].is_expression());
+    }
+
+    #[test]
+    fn statement_braced_expression_followed_by_await() {
+        let p = qp(statement, "async { 1 }.await?");
+        assert_extent!(p, (0, 18))
+    }
+
     #[test]
     fn pathed_ident_with_leading_separator() {
         let p = qp(pathed_ident, "::foo");
@@ -3370,6 +4947,18 @@ mod test {
         assert_extent!(p, (0, 28))
     }
 
+    #[test]
+    fn pathed_ident_with_turbofish_with_const() {
+        let p = qp(pathed_ident, "Buf::<16>");
+        assert_extent!(p, (0, 9))
+    }
+
+    #[test]
+    fn pathed_ident_with_turbofish_with_const_block() {
+        let p = qp(pathed_ident, "Buf::<'a, u8, { N + 1 }>");
+        assert_extent!(p, (0, 24))
+    }
+
     #[test]
     fn pathed_ident_all_space() {
         let p = qp(pathed_ident, "foo :: < Vec < u8 > , Option < bool > >");
@@ -3534,6 +5123,21 @@ mod test {
         assert_extent!(p, (0, 8))
     }
 
+    #[test]
+    fn pattern_with_c_string_literal() {
+        let p = qp(pattern, r#"c"hello""#);
+        assert!(p.kind.is_c_string());
+        assert_extent!(p, (0, 8))
+    }
+
+    #[test]
+    fn pattern_with_macro_call_has_token_trees() {
+        let p = qp(pattern, "foo!{ x }");
+        let m = unwrap_as!(p.kind, PatternKind::MacroCall);
+        assert_eq!(m.value.tokens.len(), 1);
+        assert_extent!(m.value.tokens[0], (6, 7))
+    }
+
     #[test]
     fn pattern_with_string_literal() {
         let p = qp(pattern, r#""hello""#);
@@ -3570,6 +5174,26 @@ mod test {
         assert_extent!(p, (0, 16))
     }
 
+    #[test]
+    fn pattern_with_slice_and_binding_rest() {
+        let p = qp(pattern, "[a, ref rest @ ..]");
+        assert_extent!(p, (0, 18));
+        let slice = unwrap_as!(p.kind, PatternKind::Slice);
+        let rest = slice.members.into_iter().nth(1).unwrap();
+        let rest = unwrap_as!(rest, PatternSliceMember::Subslice);
+        assert_extent!(rest, (4, 17));
+        assert_extent!(rest.name, (8, 12));
+        assert!(rest.at.is_some())
+    }
+
+    #[test]
+    fn pattern_with_slice_and_wildcard() {
+        let p = qp(pattern, "[a, .., b]");
+        assert_extent!(p, (0, 10));
+        let slice = unwrap_as!(p.kind, PatternKind::Slice);
+        assert!(slice.members[1].is_wildcard())
+    }
+
     #[test]
     fn pattern_with_reference() {
         let p = qp(pattern, "&a");
@@ -3595,6 +5219,86 @@ mod test {
         assert_extent!(p, (0, 13));
     }
 
+    #[test]
+    fn pattern_with_alternation() {
+        let p = qp(pattern, "A | B");
+        assert_extent!(p, (0, 5));
+
+        let a = unwrap_as!(p.kind, PatternKind::Alternation);
+        assert_eq!(a.patterns.len(), 2);
+        assert_extent!(a.patterns[0], (0, 1));
+        assert_extent!(a.patterns[1], (4, 5));
+    }
+
+    #[test]
+    fn pattern_with_alternation_leading_pipe() {
+        let p = qp(pattern, "| A | B");
+        assert_extent!(p, (0, 7));
+        assert!(p.kind.is_alternation());
+    }
+
+    #[test]
+    fn pattern_with_nested_alternation() {
+        let p = qp(pattern, "Some(A | B)");
+        assert_extent!(p, (0, 11));
+
+        let i = unwrap_as!(p.kind, PatternKind::Ident);
+        let mut t = i.tuple.expect("Must have a tuple");
+        let m = t.members.remove(0).into_pattern().expect("Must be a pattern");
+        assert!(m.kind.is_alternation());
+        assert_extent!(m, (5, 10));
+    }
+
+    #[test]
+    fn pattern_with_alternation_binds_looser_than_named_subpattern() {
+        let p = qp(pattern, "a @ A | B");
+        assert_extent!(p, (0, 9));
+
+        let a = unwrap_as!(p.kind, PatternKind::Alternation);
+        assert_extent!(a.patterns[0], (0, 5));
+    }
+
+    #[test]
+    fn pattern_with_parenthesis() {
+        let p = qp(pattern, "(a)");
+        assert_extent!(p, (0, 3));
+
+        let paren = unwrap_as!(p.kind, PatternKind::Parenthesized);
+        assert_extent!(paren.pattern, (1, 2));
+    }
+
+    #[test]
+    fn pattern_with_parenthesized_alternation() {
+        let p = qp(pattern, "(Ok(x) | Err(x))");
+        assert_extent!(p, (0, 16));
+
+        let paren = unwrap_as!(p.kind, PatternKind::Parenthesized);
+        assert!(paren.pattern.kind.is_alternation());
+    }
+
+    #[test]
+    fn pattern_with_tuple_of_one() {
+        let p = qp(pattern, "(a,)");
+        assert_extent!(p, (0, 4));
+        assert!(p.kind.is_tuple());
+    }
+
+    #[test]
+    fn pattern_with_tuple_of_wildcard() {
+        let p = qp(pattern, "(..)");
+        assert_extent!(p, (0, 4));
+        assert!(p.kind.is_tuple());
+    }
+
+    #[test]
+    fn pattern_with_reference_binds_tighter_than_alternation() {
+        let p = qp(pattern, "&A | B");
+        assert_extent!(p, (0, 6));
+
+        let a = unwrap_as!(p.kind, PatternKind::Alternation);
+        assert!(a.patterns[0].kind.is_reference());
+    }
+
     #[test]
     fn pattern_with_numeric_inclusive_range() {
         let p = qp(pattern, "1 ..= 10");
@@ -3625,6 +5329,57 @@ mod test {
         assert_extent!(p, (0, 10))
     }
 
+    #[test]
+    fn pattern_with_half_open_exclusive_range_from() {
+        let p = qp(pattern, "5..");
+        assert_extent!(p, (0, 3));
+        let range = unwrap_as!(p.kind, PatternKind::RangeExclusive);
+        assert!(range.start.is_some());
+        assert!(range.end.is_none())
+    }
+
+    #[test]
+    fn pattern_with_half_open_exclusive_range_to() {
+        let p = qp(pattern, "..9");
+        assert_extent!(p, (0, 3));
+        let range = unwrap_as!(p.kind, PatternKind::RangeExclusive);
+        assert!(range.start.is_none());
+        assert_extent!(range.end.unwrap(), (2, 3))
+    }
+
+    #[test]
+    fn pattern_with_half_open_inclusive_range() {
+        let p = qp(pattern, "..=-1");
+        assert_extent!(p, (0, 5));
+        let range = unwrap_as!(p.kind, PatternKind::RangeInclusive);
+        assert!(range.start.is_none());
+        assert_extent!(range.end, (3, 5))
+    }
+
+    #[test]
+    fn pattern_with_half_open_range_in_slice() {
+        let p = qp(pattern, "[0x80.., ..]");
+        assert_extent!(p, (0, 12));
+        let slice = unwrap_as!(p.kind, PatternKind::Slice);
+        assert!(slice.members[0].is_pattern());
+        assert!(slice.members[1].is_wildcard())
+    }
+
+    #[test]
+    fn pattern_with_associated_constant_range() {
+        let p = qp(pattern, "i32::MIN..=-1");
+        assert_extent!(p, (0, 13))
+    }
+
+    #[test]
+    fn pattern_with_disambiguated_range() {
+        let p = qp(pattern, "<u8>::MIN..<T as Limits>::MAX");
+        assert_extent!(p, (0, 29));
+        let range = unwrap_as!(p.kind, PatternKind::RangeExclusive);
+        assert!(range.start.unwrap().is_disambiguation());
+        assert!(range.end.unwrap().is_disambiguation())
+    }
+
     #[test]
     fn pattern_with_legacy_numeric_inclusive_range() {
         let p = qp(pattern, "1 ... 10");
@@ -3697,6 +5452,13 @@ mod test {
         assert_extent!(p, (0, 5))
     }
 
+    #[test]
+    fn pattern_with_const_block() {
+        let p = qp(pattern, "const { u8::MAX }");
+        assert_extent!(p, (0, 17));
+        assert!(p.kind.is_const_block())
+    }
+
     #[test]
     fn type_tuple() {
         let p = qp(typ, "(u8, u8)");
@@ -3715,6 +5477,72 @@ mod test {
         assert_extent!(p, (0, 4))
     }
 
+    #[test]
+    fn type_with_generics_const_literal() {
+        let p = qp(typ, "Buf<u8, 16>");
+        assert_extent!(p, (0, 11))
+    }
+
+    #[test]
+    fn type_with_generics_const_block() {
+        let p = qp(typ, "Buf<{ N + 1 }>");
+        assert_extent!(p, (0, 14))
+    }
+
+    #[test]
+    fn type_with_generics_const_negative() {
+        let p = qp(typ, "Offset<-1>");
+        assert_extent!(p, (0, 10))
+    }
+
+    #[test]
+    fn type_with_generics_associated_type_bound() {
+        let p = qp(typ, "impl Iterator<Item: Debug + Send>");
+        assert_extent!(p, (0, 33))
+    }
+
+    #[test]
+    fn type_generics_angle_member_associated_type_bound() {
+        let p = qp(typ_generics_angle_member, "Item: Debug + Send");
+        let p = unwrap_as!(p, TypeGenericsAngleMember::AssociatedTypeBound);
+        assert_extent!(p, (0, 18));
+        assert_eq!(p.bounds.types.len(), 2);
+    }
+
+    #[test]
+    fn type_with_generics_generic_associated_type() {
+        let p = qp(typ, "Lending<Item<'a> = &'a u8>");
+        assert_extent!(p, (0, 26))
+    }
+
+    #[test]
+    fn type_generics_angle_member_generic_associated_type() {
+        let p = qp(typ_generics_angle_member, "Item<'a> = &'a u8");
+        let p = unwrap_as!(p, TypeGenericsAngleMember::GenericAssociatedType);
+        assert_extent!(p, (0, 17));
+        assert_extent!(p.generics, (4, 8));
+    }
+
+    #[test]
+    fn turbofish_with_lifetime_type_and_const() {
+        let p = qp(turbofish, "::<'a, u8, 3>");
+        assert_extent!(p, (0, 13));
+        assert_eq!(p.members.len(), 3);
+        assert!(p.members[2].is_const());
+    }
+
+    #[test]
+    fn turbofish_with_associated_type_fails() {
+        let (err_loc, _) = unwrap_progress_err(parse_full(turbofish, "::<Item = u8>"));
+        assert_eq!(err_loc, 3);
+    }
+
+    #[test]
+    fn turbofish_with_associated_type_bound_fails() {
+        let (err_loc, _) = unwrap_progress_err(parse_full(turbofish, "::<Item: Send>"));
+        assert_eq!(err_loc, 3);
+    }
+
     #[test]
     fn type_with_generics_all_space() {
         let p = qp(typ, "A < T >");
@@ -4075,6 +5903,33 @@ mod test {
         assert_extent!(p, (0, 4))
     }
 
+    #[test]
+    fn ident_safe_is_contextual() {
+        let p = qp(statement, "let safe = safe::check(safe);");
+        assert_extent!(p, (0, 28))
+    }
+
+    #[test]
+    fn ident_raw() {
+        let p = qp(ident, "r#type");
+        assert_extent!(p, (0, 6));
+        assert_eq!(p.name, Extent(2, 6));
+        assert!(p.is_raw);
+    }
+
+    #[test]
+    fn ident_not_raw() {
+        let p = qp(ident, "r");
+        assert_eq!(p.name, Extent(0, 1));
+        assert!(!p.is_raw);
+    }
+
+    #[test]
+    fn item_function_with_raw_ident() {
+        let p = qp(item, "fn r#match(r#in: r#type) {}");
+        assert_extent!(p, (0, 27))
+    }
+
     #[test]
     fn lifetime_ident() {
         let p = qp(lifetime, "'a");
@@ -4087,6 +5942,23 @@ mod test {
         assert_extent!(p, (0, 7))
     }
 
+    #[test]
+    fn lifetime_raw() {
+        let p = qp(lifetime, "'r#a");
+        assert_extent!(p, (0, 4));
+        assert_extent!(p.name, (0, 4));
+        assert_eq!(p.name.name, Extent(3, 4));
+        assert!(p.name.is_raw);
+    }
+
+    #[test]
+    fn lifetime_name_includes_apostrophe() {
+        let p = qp(lifetime, "'a");
+        assert_extent!(p.name, (0, 2));
+        assert_eq!(p.name.name, Extent(1, 2));
+        assert!(!p.name.is_raw);
+    }
+
     #[test]
     fn generic_declarations_() {
         let p = qp(generic_declarations, "<A>");
@@ -4123,6 +5995,32 @@ mod test {
         assert_extent!(p, (0, 21))
     }
 
+    #[test]
+    fn generic_declarations_with_const() {
+        let p = qp(generic_declarations, "<const N: usize>");
+        assert_extent!(p, (0, 16));
+        assert!(p.members[0].is_const());
+    }
+
+    #[test]
+    fn generic_declarations_with_const_default() {
+        let p = qp(generic_declarations, "<const N: usize = 4, const M: i8 = -1, const O: u8 = { 1 + 2 }>");
+        assert_extent!(p, (0, 63));
+        assert_eq!(p.members.len(), 3);
+    }
+
+    #[test]
+    fn generic_declarations_keep_source_order() {
+        let p = qp(generic_declarations, "<'a, T, const N: usize, U>");
+        assert_extent!(p, (0, 26));
+
+        assert!(p.members[0].is_lifetime());
+        assert!(p.members[1].is_type());
+        assert!(p.members[2].is_const());
+        assert!(p.members[3].is_type());
+        assert_extent!(p.members[2], (8, 22));
+    }
+
     #[test]
     fn generic_declarations_all_space() {
         let p = qp(generic_declarations, "< 'a : 'b , A : Foo >");
@@ -6,9 +6,9 @@ type TestResult<T> = Result<(usize, T), (usize, Vec<Error>)>;
 pub(crate) fn parse_full<'s, F, T>(f: F, s: &'s str) -> TestResult<T>
     where F: for<'a> FnOnce(&mut Master<'a>, Point<'a>) -> Progress<'a, T>
 {
-    let (_ws, tokens) = crate::extract_whitespace(s).expect("Unable to tokenize");
+    let (_ws, _prefix, tokens) = crate::extract_whitespace(crate::tokenizer::Tokens::new(s)).expect("Unable to tokenize");
 
-    let mut pm = Master::with_state(State::new());
+    let mut pm = Master::with_state(State::new(s));
     let pt = Point::new(&tokens);
     let r = f(&mut pm, pt);
     match pm.finish(r) {
@@ -5,7 +5,7 @@ use unicode_xid::UnicodeXID;
 use peresil;
 use peresil::combinators::*;
 
-use crate::{Extent, HumanTextError};
+use crate::{Extent, HumanTextError, write_expected};
 use crate::combinators::{not, peek};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Decompose)]
@@ -74,6 +74,7 @@ pub enum Token {
     As(Extent),
     Async(Extent),
     Auto(Extent),
+    Await(Extent),
     Box(Extent),
     Break(Extent),
     Const(Extent),
@@ -91,6 +92,7 @@ pub enum Token {
     In(Extent),
     Let(Extent),
     Loop(Extent),
+    MacroRules(Extent),
     Match(Extent),
     Mod(Extent),
     Move(Extent),
@@ -98,6 +100,7 @@ pub enum Token {
     Pub(Extent),
     Ref(Extent),
     Return(Extent),
+    Safe(Extent),
     SelfIdent(Extent),
     Static(Extent),
     Struct(Extent),
@@ -116,9 +119,11 @@ pub enum Token {
     Byte(Extent),
     ByteString(Extent),
     ByteStringRaw(Extent),
+    CString(Extent),
+    CStringRaw(Extent),
 
     // Other
-    Ident(Extent),
+    Ident(Ident),
     Number(Number),
     Whitespace(Extent),
     CommentLine(Extent),
@@ -127,7 +132,9 @@ pub enum Token {
     DocCommentInnerLine(Extent),
     DocCommentOuterBlock(Extent),
     DocCommentInnerBlock(Extent),
-    Lifetime(Extent),
+    Lifetime(Ident),
+    ByteOrderMark(Extent),
+    Shebang(Extent),
     EndOfFile(Extent),
 }
 
@@ -143,17 +150,21 @@ impl Token {
             Async(s)               |
             At(s)                  |
             Auto(s)                |
+            Await(s)               |
             Backslash(s)           |
             Bang(s)                |
             Box(s)                 |
             Break(s)               |
             Byte(s)                |
+            ByteOrderMark(s)       |
             ByteString(s)          |
             ByteStringRaw(s)       |
             Caret(s)               |
             CaretEquals(s)         |
             Character(s)           |
             Colon(s)               |
+            CString(s)             |
+            CStringRaw(s)          |
             Comma(s)               |
             CommentLine(s)         |
             CommentBlock(s)        |
@@ -185,7 +196,6 @@ impl Token {
             For(s)                 |
             GreaterThanOrEquals(s) |
             Hash(s)                |
-            Ident(s)               |
             If(s)                  |
             Impl(s)                |
             In(s)                  |
@@ -195,8 +205,8 @@ impl Token {
             LeftSquare(s)          |
             LessThanOrEquals(s)    |
             Let(s)                 |
-            Lifetime(s)            |
             Loop(s)                |
+            MacroRules(s)          |
             Match(s)               |
             Minus(s)               |
             MinusEquals(s)         |
@@ -219,8 +229,10 @@ impl Token {
             RightCurly(s)          |
             RightParen(s)          |
             RightSquare(s)         |
+            Safe(s)                |
             SelfIdent(s)           |
             Semicolon(s)           |
+            Shebang(s)             |
             ShiftLeftEquals(s)     |
             ShiftRightEquals(s)    |
             Slash(s)               |
@@ -242,9 +254,125 @@ impl Token {
             While(s)               |
             Whitespace(s)          => s,
 
-            Number(s) => s.extent(),
+            Ident(s)    => s.extent,
+            Lifetime(s) => s.extent,
+            Number(s)   => s.extent(),
         }
     }
+
+    /// The extent of any keyword, strict or contextual
+    pub fn into_keyword(self) -> Option<Extent> {
+        use self::Token::*;
+
+        match self {
+            As(s)         |
+            Async(s)      |
+            Auto(s)       |
+            Await(s)      |
+            Box(s)        |
+            Break(s)      |
+            Const(s)      |
+            Continue(s)   |
+            Crate(s)      |
+            Default(s)    |
+            Dyn(s)        |
+            Else(s)       |
+            Enum(s)       |
+            Extern(s)     |
+            Fn(s)         |
+            For(s)        |
+            If(s)         |
+            Impl(s)       |
+            In(s)         |
+            Let(s)        |
+            Loop(s)       |
+            MacroRules(s) |
+            Match(s)      |
+            Mod(s)        |
+            Move(s)       |
+            Mut(s)        |
+            Pub(s)        |
+            Ref(s)        |
+            Return(s)     |
+            Safe(s)       |
+            SelfIdent(s)  |
+            Static(s)     |
+            Struct(s)     |
+            Trait(s)      |
+            Type(s)       |
+            Union(s)      |
+            Unsafe(s)     |
+            Use(s)        |
+            Where(s)      |
+            While(s)      => Some(s),
+            _ => None,
+        }
+    }
+
+    /// A symbol that may be combined with the following one, such as
+    /// each `>` in `>>=`. Paired delimiters other than `<` and `>`
+    /// are not punctuation.
+    pub fn is_punctuation(&self) -> bool {
+        use self::Token::*;
+
+        matches!(*self,
+            LeftAngle(_)           |
+            RightAngle(_)          |
+            Ampersand(_)           |
+            AmpersandEquals(_)     |
+            Asterisk(_)            |
+            At(_)                  |
+            Backslash(_)           |
+            Bang(_)                |
+            Caret(_)               |
+            CaretEquals(_)         |
+            Colon(_)               |
+            Comma(_)               |
+            DivideEquals(_)        |
+            Dollar(_)              |
+            DoubleAmpersand(_)     |
+            DoubleColon(_)         |
+            DoubleEquals(_)        |
+            DoubleLeftAngle(_)     |
+            DoublePeriod(_)        |
+            DoublePeriodEquals(_)  |
+            DoublePipe(_)          |
+            DoubleRightAngle(_)    |
+            Equals(_)              |
+            GreaterThanOrEquals(_) |
+            Hash(_)                |
+            LessThanOrEquals(_)    |
+            Minus(_)               |
+            MinusEquals(_)         |
+            NotEqual(_)            |
+            Percent(_)             |
+            PercentEquals(_)       |
+            Period(_)              |
+            Pipe(_)                |
+            PipeEquals(_)          |
+            Plus(_)                |
+            PlusEquals(_)          |
+            QuestionMark(_)        |
+            Semicolon(_)           |
+            ShiftLeftEquals(_)     |
+            ShiftRightEquals(_)    |
+            Slash(_)               |
+            ThickArrow(_)          |
+            ThinArrow(_)           |
+            Tilde(_)               |
+            TimesEquals(_)         |
+            TriplePeriod(_)
+        )
+    }
+}
+
+/// An identifier or lifetime, possibly written in raw form (`r#type`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Ident {
+    pub extent: Extent,
+    /// The identifier without any `'` or `r#` prefix
+    pub name: Extent,
+    pub is_raw: bool,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Decompose)]
@@ -319,23 +447,79 @@ number!(NumberDecimal);
 number!(NumberHexadecimal);
 number!(NumberOctal);
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub(crate) enum Error {
+impl NumberDecimal {
+    /// Splits a number like `0.1` into the integer before the period,
+    /// the period, and the integer after it (if any). The tokenizer
+    /// produces these for nested tuple field accesses like `x.0.1`.
+    pub(crate) fn split_tuple_indices(&self) -> Option<(NumberDecimal, Extent, Option<NumberDecimal>)> {
+        if self.exponent.is_some() || self.type_suffix.is_some() || self.underscores != 0 {
+            return None;
+        }
+
+        let Extent(s, e) = self.fractional?;
+        let integer = |extent| NumberDecimal {
+            extent,
+            integral: extent,
+            fractional: None,
+            exponent: None,
+            type_suffix: None,
+            underscores: 0,
+        };
+
+        let after = if s + 1 < e { Some(integer(Extent(s + 1, e))) } else { None };
+        Some((integer(self.integral), Extent(s, s + 1), after))
+    }
+}
+
+/// A single reason that tokenizing failed, usually something that
+/// was expected at the failure location.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Error {
     Literal(&'static str),
     ExpectedIdent,
     ExpectedNumber,
     ExpectedHex,
     ExpectedWhitespace,
     ExpectedComment,
+    ExpectedByteOrderMark,
+    ExpectedShebang,
     ExpectedCharacter,
     UnterminatedRawString,
+    UnterminatedComment,
+    NulInCString,
+    InvalidRawIdent,
 
     // Internal parsing errors, should be recovered
     InvalidFollowForFractionalNumber,
 }
 
 impl peresil::Recoverable for Error {
-    fn recoverable(&self) -> bool { true }
+    fn recoverable(&self) -> bool {
+        !matches!(*self, Error::UnterminatedComment | Error::NulInCString | Error::InvalidRawIdent)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match *self {
+            Error::Literal(l)                       => return write!(f, "`{}`", l),
+            Error::ExpectedIdent                    => "an identifier",
+            Error::ExpectedNumber                   => "a number",
+            Error::ExpectedHex                      => "a hexadecimal digit",
+            Error::ExpectedWhitespace               => "whitespace",
+            Error::ExpectedComment                  => "a comment",
+            Error::ExpectedByteOrderMark            => "a byte order mark",
+            Error::ExpectedShebang                  => "`#!`",
+            Error::ExpectedCharacter                => "a character",
+            Error::UnterminatedRawString            => "the end of the raw string",
+            Error::UnterminatedComment              => "the end of the block comment",
+            Error::NulInCString                     => "a C string without NUL characters",
+            Error::InvalidRawIdent                  => "an identifier that may be raw",
+            Error::InvalidFollowForFractionalNumber => "the end of the number",
+        };
+        f.write_str(description)
+    }
 }
 
 /// Information about a tokenization error
@@ -350,8 +534,27 @@ impl ErrorDetail {
     pub fn with_text<'a>(&'a self, text: &'a str) -> ErrorDetailText<'a> {
         ErrorDetailText { detail: self, text }
     }
+
+    /// The byte offset into the source code where tokenizing failed
+    pub fn location(&self) -> usize {
+        self.location
+    }
+
+    /// Everything that would have allowed tokenizing to continue
+    pub fn expected(&self) -> &BTreeSet<Error> {
+        &self.errors
+    }
 }
 
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unable to tokenize text at byte {}, expected ", self.location)?;
+        write_expected(f, &self.errors)
+    }
+}
+
+impl std::error::Error for ErrorDetail {}
+
 /// Information about a tokenization error including original source code
 #[derive(Debug)]
 pub struct ErrorDetailText<'a> {
@@ -368,7 +571,7 @@ impl<'a> fmt::Display for ErrorDetailText<'a> {
         writeln!(f, "{:>width$}", "^", width = human.column)?;
         writeln!(f, "Expected:")?;
         for e in &self.detail.errors {
-            writeln!(f, "  {:?}", e)?; // TODO: should be Display
+            writeln!(f, "  {}", e)?;
         }
         Ok(())
     }
@@ -381,6 +584,7 @@ type Progress<'s, T> = peresil::Progress<Point<'s>, T, Error>;
 pub struct Tokens<'s> {
     pm: Master<'s>,
     pt: Point<'s>,
+    is_start_of_file: bool,
     is_exhausted: bool,
 }
 
@@ -389,9 +593,16 @@ impl<'s> Tokens<'s> {
         Tokens {
             pm: Master::new(),
             pt: Point::new(code),
+            is_start_of_file: true,
             is_exhausted: false,
         }
     }
+
+    /// Tokenizes code that is not an entire file, so a leading byte
+    /// order mark or `#!` line is not treated specially.
+    pub fn fragment(code: &'s str) -> Self {
+        Tokens { is_start_of_file: false, ..Tokens::new(code) }
+    }
 }
 
 impl<'s> Iterator for Tokens<'s> {
@@ -407,6 +618,17 @@ impl<'s> Iterator for Tokens<'s> {
             return Some(Ok(Token::EndOfFile(Extent(self.pt.offset, self.pt.offset))));
         }
 
+        if self.is_start_of_file {
+            let tok = start_of_file_token(&mut self.pm, self.pt);
+            let tok = self.pm.finish(tok);
+
+            if let peresil::Status::Success(value) = tok.status {
+                self.pt = tok.point;
+                return Some(Ok(value));
+            }
+            self.is_start_of_file = false;
+        }
+
         let tok = single_token(&mut self.pm, self.pt);
         let tok = self.pm.finish(tok);
 
@@ -426,6 +648,59 @@ impl<'s> Iterator for Tokens<'s> {
     }
 }
 
+// These may only appear before any other tokens in the file
+fn start_of_file_token<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Token> {
+    pm.alternate(pt)
+        .one(map(byte_order_mark, Token::ByteOrderMark))
+        .one(map(shebang, Token::Shebang))
+        .finish()
+}
+
+fn byte_order_mark<'s>(_pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
+    if pt.offset != 0 {
+        return Progress::failure(pt, Error::ExpectedByteOrderMark);
+    }
+
+    let (pt2, _) = try_parse!(pt.consume_literal("\u{feff}").map_err(|_| Error::ExpectedByteOrderMark));
+    Progress::success(pt2, ex(pt, pt2))
+}
+
+fn shebang<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
+    let spt = pt;
+    let (mut tail, _) = try_parse!(literal("#!")(pm, pt).map_err(|_| Error::ExpectedShebang));
+
+    // Like rustc, `#!` followed by a `[` (ignoring whitespace and
+    // non-doc comments) starts an inner attribute instead
+    loop {
+        let ws = pm.alternate(tail)
+            .one(map(whitespace, |_| ()))
+            .one(comment_line_or_block)
+            .finish();
+
+        match ws.status {
+            peresil::Status::Success(_) => tail = ws.point,
+            peresil::Status::Failure(_) => break,
+        }
+    }
+    if tail.s.starts_with('[') {
+        return Progress::failure(pt, Error::ExpectedShebang);
+    }
+
+    let eol = pt.s.find("\n").unwrap_or(pt.s.len());
+    let (pt, _) = try_parse!(spt.consume_to(Some(eol)).map_err(|_| Error::ExpectedShebang));
+    Progress::success(pt, ex(spt, pt))
+}
+
+fn comment_line_or_block<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, ()> {
+    match comment_or_doc_comment(pm, pt) {
+        peresil::Progress { status: peresil::Status::Success(Token::CommentLine(_)), point } |
+        peresil::Progress { status: peresil::Status::Success(Token::CommentBlock(_)), point } => {
+            Progress::success(point, ())
+        }
+        _ => Progress::failure(pt, Error::ExpectedComment),
+    }
+}
+
 fn single_token<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Token> {
     pm.alternate(pt)
         .one(comment_or_doc_comment)
@@ -435,6 +710,8 @@ fn single_token<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Token> {
         .one(map(byte, Token::Byte))
         .one(map(byte_string, Token::ByteString))
         .one(map(byte_string_raw, Token::ByteStringRaw))
+        .one(map(c_string, Token::CString))
+        .one(map(c_string_raw, Token::CStringRaw))
         .one(map(lifetime, Token::Lifetime))
 
         // Symbols; longest first
@@ -505,11 +782,19 @@ fn single_token<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Token> {
 }
 
 fn keyword_or_ident<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Token> {
+    pm.alternate(pt)
+        .one(map(raw_ident, Token::Ident))
+        .one(keyword_or_plain_ident)
+        .finish()
+}
+
+fn keyword_or_plain_ident<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Token> {
     ident_raw(pm, pt).map(|(s, extent)| {
         match s {
             "as" => Token::As(extent),
             "async" => Token::Async(extent),
             "auto" => Token::Auto(extent),
+            "await" => Token::Await(extent),
             "box" => Token::Box(extent),
             "break" => Token::Break(extent),
             "const" => Token::Const(extent),
@@ -527,6 +812,7 @@ fn keyword_or_ident<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Toke
             "in" => Token::In(extent),
             "let" => Token::Let(extent),
             "loop" => Token::Loop(extent),
+            "macro_rules" => Token::MacroRules(extent),
             "match" => Token::Match(extent),
             "mod" => Token::Mod(extent),
             "move" => Token::Move(extent),
@@ -534,6 +820,7 @@ fn keyword_or_ident<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Toke
             "pub" => Token::Pub(extent),
             "ref" => Token::Ref(extent),
             "return" => Token::Return(extent),
+            "safe" => Token::Safe(extent),
             "self" => Token::SelfIdent(extent),
             "static" => Token::Static(extent),
             "struct" => Token::Struct(extent),
@@ -544,7 +831,7 @@ fn keyword_or_ident<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Toke
             "unsafe" => Token::Unsafe(extent),
             "where" => Token::Where(extent),
             "while" => Token::While(extent),
-            _ => Token::Ident(extent)
+            _ => Token::Ident(Ident { extent, name: extent, is_raw: false })
         }
     })
 }
@@ -553,6 +840,21 @@ fn ident<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
     ident_raw(pm, pt).map(|(_, e)| e)
 }
 
+// Path segment keywords and `_` cannot be raw identifiers
+const NOT_RAW_IDENTS: &[&str] = &["_", "crate", "self", "Self", "super"];
+
+fn raw_ident<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Ident> {
+    let spt = pt;
+    let (name_pt, _) = try_parse!(literal("r#")(pm, pt));
+    let (pt, (text, name)) = try_parse!(ident_raw(pm, name_pt));
+
+    if NOT_RAW_IDENTS.contains(&text) {
+        return Progress::failure(name_pt, Error::InvalidRawIdent);
+    }
+
+    Progress::success(pt, Ident { extent: ex(spt, pt), name, is_raw: true })
+}
+
 fn ident_raw<'s>(_pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, (&'s str, Extent)> {
     let mut ci = pt.s.chars();
     let mut idx = 0;
@@ -708,15 +1010,15 @@ fn comment_or_doc_comment<'s>(_pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'
         let (pt, _) = try_parse!(spt.consume_to(Some(eol)).map_err(|_| Error::ExpectedComment));
         Progress::success(pt, Token::CommentLine(ex(spt, pt)))
     } else if pt.s.starts_with("/**") && !pt.s.starts_with("/***") && !pt.s.starts_with("/**/") {
-        let eol = pt.s[3..].find("*/").map(|x| 3 + x + 2).unwrap_or(pt.s.len());
+        let (_, eol) = try_parse!(block_comment_end(pt));
         let (pt, _) = try_parse!(spt.consume_to(Some(eol)).map_err(|_| Error::ExpectedComment));
         Progress::success(pt, Token::DocCommentOuterBlock(ex(spt, pt)))
     } else if pt.s.starts_with("/*!") {
-        let eol = pt.s[3..].find("*/").map(|x| 3 + x + 2).unwrap_or(pt.s.len());
+        let (_, eol) = try_parse!(block_comment_end(pt));
         let (pt, _) = try_parse!(spt.consume_to(Some(eol)).map_err(|_| Error::ExpectedComment));
         Progress::success(pt, Token::DocCommentInnerBlock(ex(spt, pt)))
     } else if pt.s.starts_with("/*") {
-        let eol = pt.s[2..].find("*/").map(|x| 2 + x + 2).unwrap_or(pt.s.len());
+        let (_, eol) = try_parse!(block_comment_end(pt));
         let (pt, _) = try_parse!(spt.consume_to(Some(eol)).map_err(|_| Error::ExpectedComment));
         Progress::success(pt, Token::CommentBlock(ex(spt, pt)))
     } else {
@@ -724,6 +1026,32 @@ fn comment_or_doc_comment<'s>(_pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'
     }
 }
 
+// Block comments nest, so we have to balance each `/*` with a `*/`
+fn block_comment_end<'s>(pt: Point<'s>) -> Progress<'s, usize> {
+    let s = pt.s.as_bytes();
+    let mut depth = 0;
+    let mut idx = 0;
+
+    while idx + 1 < s.len() {
+        match (s[idx], s[idx + 1]) {
+            (b'/', b'*') => {
+                depth += 1;
+                idx += 2;
+            }
+            (b'*', b'/') => {
+                depth -= 1;
+                idx += 2;
+                if depth == 0 {
+                    return Progress::success(pt, idx);
+                }
+            }
+            _ => idx += 1,
+        }
+    }
+
+    Progress::failure(pt, Error::UnterminatedComment)
+}
+
 fn character<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
     sequence!(pm, pt, {
         spt = point;
@@ -861,6 +1189,82 @@ fn raw_string_tail<'s>(hashes: usize) -> impl Fn(&mut Master<'s>, Point<'s>) ->
     }
 }
 
+fn c_string<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
+    sequence!(pm, pt, {
+        spt = point;
+        _   = literal("c");
+        _   = literal("\"");
+        _   = c_string_char;
+        _   = literal("\"");
+    }, |_, pt| ex(spt, pt))
+}
+
+fn c_string_char<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, &'s str> {
+    let (tail, s) = try_parse!(string_char(pm, pt));
+
+    match c_string_nul_position(s, true) {
+        Some(i) => Progress::failure(Point { s: &pt.s[i..], offset: pt.offset + i }, Error::NulInCString),
+        None => Progress::success(tail, s),
+    }
+}
+
+fn c_string_raw<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
+    sequence!(pm, pt, {
+        spt = point;
+        _   = literal("c");
+        _   = literal("r");
+        h   = zero_or_more(literal("#"));
+        _   = literal("\"");
+        _   = c_string_raw_tail(h.len());
+    }, |_, pt| ex(spt, pt))
+}
+
+fn c_string_raw_tail<'s>(hashes: usize) -> impl Fn(&mut Master<'s>, Point<'s>) ->
+    Progress<'s, &'s str>
+{
+    let tail = raw_string_tail(hashes);
+
+    move |pm, pt| {
+        let (after, s) = try_parse!(tail(pm, pt));
+
+        match c_string_nul_position(s, false) {
+            Some(i) => Progress::failure(Point { s: &pt.s[i..], offset: pt.offset + i }, Error::NulInCString),
+            None => Progress::success(after, s),
+        }
+    }
+}
+
+// C strings cannot contain a NUL, either literally or as an escape
+// (`\0`, `\x00`, `\u{0}`)
+fn c_string_nul_position(s: &str, allow_escapes: bool) -> Option<usize> {
+    let mut escaped = false;
+
+    for (i, c) in s.char_indices() {
+        match (escaped, c) {
+            (_, '\0') => return Some(i),
+            (true, _) => escaped = false,
+            (false, '\\') if allow_escapes => {
+                let escape = &s[i + 1..];
+
+                if escape.starts_with('0') || escape.starts_with("x00") {
+                    return Some(i);
+                }
+                if let Some(digits) = escape.strip_prefix("u{") {
+                    let digits = digits.split('}').next().unwrap_or("");
+                    if digits.chars().all(|c| c == '0' || c == '_') {
+                        return Some(i);
+                    }
+                }
+
+                escaped = true;
+            }
+            (false, _) => { /* Next char */ },
+        }
+    }
+
+    None
+}
+
 fn byte<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
     sequence!(pm, pt, {
         spt = point;
@@ -885,12 +1289,13 @@ fn byte_string_raw<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Exten
     }, |_, pt| ex(spt, pt))
 }
 
-fn lifetime<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Extent> {
+fn lifetime<'s>(pm: &mut Master<'s>, pt: Point<'s>) -> Progress<'s, Ident> {
     sequence!(pm, pt, {
-        spt = point;
-        _   = literal("'");
-        _   = ident;
-    }, |_, pt| ex(spt, pt))
+        spt    = point;
+        _      = literal("'");
+        is_raw = optional(literal("r#"));
+        name   = ident;
+    }, |_, pt| Ident { extent: ex(spt, pt), name, is_raw: is_raw.is_some() })
 }
 
 fn literal<'s>(expected: &'static str) ->
@@ -943,10 +1348,48 @@ mod test {
         assert_eq!(s, (0, 3))
     }
 
+    #[test]
+    fn await_is_a_keyword() {
+        let s = tokenize_as!("await", Token::Await);
+        assert_eq!(s, (0, 5))
+    }
+
     #[test]
     fn ident_can_have_keyword_substring() {
         let s = tokenize_as!("form", Token::Ident);
-        assert_eq!(s, (0, 4))
+        assert_eq!(s.extent, (0, 4))
+    }
+
+    #[test]
+    fn raw_ident() {
+        let s = tokenize_as!("r#type", Token::Ident);
+        assert_eq!(s.extent, (0, 6));
+        assert_eq!(s.name, (2, 6));
+        assert!(s.is_raw);
+    }
+
+    #[test]
+    fn raw_ident_rejects_path_keywords_and_underscore() {
+        for code in &["r#crate", "r#self", "r#Self", "r#super", "r#_"] {
+            let e = Tokens::new(code).collect::<Result<Vec<_>, _>>();
+            let e = e.expect_err("Tokenization should fail");
+            assert_eq!(e.location, 2, "{}", code);
+            assert!(e.errors.contains(&Error::InvalidRawIdent), "{}", code);
+        }
+    }
+
+    #[test]
+    fn raw_ident_allows_prefix_of_path_keyword() {
+        let s = tokenize_as!("r#selfish", Token::Ident);
+        assert_eq!(s.name, (2, 9));
+    }
+
+    #[test]
+    fn raw_lifetime() {
+        let s = tokenize_as!("'r#a", Token::Lifetime);
+        assert_eq!(s.extent, (0, 4));
+        assert_eq!(s.name, (3, 4));
+        assert!(s.is_raw);
     }
 
     #[test]
@@ -978,13 +1421,13 @@ mod test {
         let toks = tok("impl<'a> Foo<'a> for Bar<'a> { }");
 
         let s = unwrap_as!(toks[2], Token::Lifetime);
-        assert_eq!(s, (5, 7));
+        assert_eq!(s.extent, (5, 7));
 
         let s = unwrap_as!(toks[7], Token::Lifetime);
-        assert_eq!(s, (13, 15));
+        assert_eq!(s.extent, (13, 15));
 
         let s = unwrap_as!(toks[14], Token::Lifetime);
-        assert_eq!(s, (25, 27));
+        assert_eq!(s.extent, (25, 27));
     }
 
     #[test]
@@ -1005,6 +1448,40 @@ mod test {
         assert_eq!(s, (0, 6));
     }
 
+    #[test]
+    fn c_string() {
+        let s = tokenize_as!(r#"c"a\x01\u{1F63B}""#, Token::CString);
+        assert_eq!(s, (0, 17));
+    }
+
+    #[test]
+    fn c_string_raw() {
+        let s = tokenize_as!(r###"cr#"a\0"#"###, Token::CStringRaw);
+        assert_eq!(s, (0, 9));
+    }
+
+    #[test]
+    fn c_string_escaped_backslash_before_zero() {
+        let s = tokenize_as!(r#"c"\\0""#, Token::CString);
+        assert_eq!(s, (0, 6));
+    }
+
+    #[test]
+    fn c_string_rejects_nul_escapes() {
+        for &(code, location) in &[
+            (r#"c"a\0""#, 3),
+            (r#"c"ab\x00""#, 4),
+            (r#"c"\u{0_0}""#, 2),
+            ("c\"a\0\"", 3),
+            ("cr\"a\0\"", 4),
+        ] {
+            let e = Tokens::new(code).collect::<Result<Vec<_>, _>>();
+            let e = e.expect_err("Tokenization should fail");
+            assert_eq!(e.location, location, "{}", code);
+            assert!(e.errors.contains(&Error::NulInCString));
+        }
+    }
+
     #[test]
     fn byte_string_raw() {
         let s = tokenize_as!(r#"br"abc""#, Token::ByteStringRaw);
@@ -1126,7 +1603,7 @@ mod test {
     #[test]
     fn number_decimal_with_leading_spacer_is_an_ident() {
         let s = tokenize_as!("_42", Token::Ident);
-        assert_eq!(s, (0, 3));
+        assert_eq!(s.extent, (0, 3));
     }
 
     #[test]
@@ -1154,7 +1631,7 @@ mod test {
         assert_eq!(s, (1, 2));
 
         let s = unwrap_as!(toks[2], Token::Ident);
-        assert_eq!(s, (2, 5));
+        assert_eq!(s.extent, (2, 5));
     }
 
     #[test]
@@ -1181,6 +1658,20 @@ mod test {
         assert_eq!(s, (0, 4))
     }
 
+    #[test]
+    fn comment_block_nested() {
+        let s = tokenize_as!("/* outer /* inner */ still comment */", Token::CommentBlock);
+        assert_eq!(s, (0, 37))
+    }
+
+    #[test]
+    fn comment_block_unterminated() {
+        let e = Tokens::new("a /* b /* c */").collect::<Result<Vec<_>, _>>();
+        let e = e.expect_err("Tokenization should fail");
+        assert_eq!(e.location, 2);
+        assert!(e.errors.contains(&Error::UnterminatedComment));
+    }
+
     #[test]
     fn doc_comment_outer_block() {
         let s = tokenize_as!("/** hi */", Token::DocCommentOuterBlock);
@@ -1193,6 +1684,12 @@ mod test {
         assert_eq!(s, (0, 9))
     }
 
+    #[test]
+    fn doc_comment_block_nested() {
+        let s = tokenize_as!("/** a /* b */ c */", Token::DocCommentOuterBlock);
+        assert_eq!(s, (0, 18))
+    }
+
     #[test]
     fn doc_comment_outer_line() {
         let s = tokenize_as!("/// hi", Token::DocCommentOuterLine);
@@ -1205,6 +1702,23 @@ mod test {
         assert_eq!(s, (0, 6))
     }
 
+    #[test]
+    fn shebang_only_at_start_of_file() {
+        let toks = tok("#!/bin/sh\n#!/bin/sh");
+
+        let s = unwrap_as!(toks[0], Token::Shebang);
+        assert_eq!(s, (0, 9));
+
+        let s = unwrap_as!(toks[2], Token::Hash);
+        assert_eq!(s, (10, 11));
+    }
+
+    #[test]
+    fn fragment_has_no_shebang() {
+        let toks: Vec<_> = Tokens::fragment("#!/bin/sh").collect::<Result<_, _>>().expect("Unable to tokenize");
+        assert!(toks[0].is_hash());
+    }
+
     #[test]
     fn end_of_file() {
         let s = tokenize_as!("", Token::EndOfFile);
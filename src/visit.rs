@@ -125,6 +125,7 @@ pub trait Visitor<'ast> {
     fn visit_as_type(&mut self, _: &'ast AsType) -> Control { Control::Continue }
     fn visit_ascription(&mut self, _: &'ast Ascription) -> Control { Control::Continue }
     fn visit_associated_type(&mut self, _: &'ast AssociatedType) -> Control { Control::Continue }
+    fn visit_associated_type_bound(&mut self, _: &'ast AssociatedTypeBound) -> Control { Control::Continue }
     fn visit_async_block(&mut self, _: &'ast AsyncBlock) -> Control { Control::Continue }
     fn visit_attribute(&mut self, _: &'ast Attribute) -> Control { Control::Continue }
     fn visit_attribute_literal(&mut self, _: &'ast AttributeLiteral) -> Control { Control::Continue }
@@ -133,24 +134,26 @@ pub trait Visitor<'ast> {
     fn visit_attributed_enum_variant(&mut self, _: &'ast Attributed<EnumVariant>) -> Control { Control::Continue }
     fn visit_attributed_expression(&mut self, _: &'ast Attributed<Expression>) -> Control { Control::Continue }
     fn visit_attributed_extern_block_member(&mut self, _: &'ast Attributed<ExternBlockMember>) -> Control { Control::Continue }
-    fn visit_attributed_generic_declaration_lifetime(&mut self, _: &'ast Attributed<GenericDeclarationLifetime>) -> Control { Control::Continue }
-    fn visit_attributed_generic_declaration_type(&mut self, _: &'ast Attributed<GenericDeclarationType>) -> Control { Control::Continue }
+    fn visit_attributed_generic_declaration(&mut self, _: &'ast Attributed<GenericDeclaration>) -> Control { Control::Continue }
     fn visit_attributed_impl_member(&mut self, _: &'ast Attributed<ImplMember>) -> Control { Control::Continue }
     fn visit_attributed_item(&mut self, _: &'ast Attributed<Item>) -> Control { Control::Continue }
     fn visit_attributed_struct_definition_field_named(&mut self, _: &'ast Attributed<StructDefinitionFieldNamed>) -> Control { Control::Continue }
     fn visit_attributed_struct_definition_field_unnamed(&mut self, _: &'ast Attributed<StructDefinitionFieldUnnamed>) -> Control { Control::Continue }
     fn visit_attributed_trait_member(&mut self, _: &'ast Attributed<TraitMember>) -> Control { Control::Continue }
+    fn visit_await(&mut self, _: &'ast Await) -> Control { Control::Continue }
     fn visit_binary(&mut self, _: &'ast Binary) -> Control { Control::Continue }
     fn visit_block(&mut self, _: &'ast Block) -> Control { Control::Continue }
     fn visit_break(&mut self, _: &'ast Break) -> Control { Control::Continue }
     fn visit_byte(&mut self, _: &'ast Byte) -> Control { Control::Continue }
     fn visit_byte_string(&mut self, _: &'ast ByteString) -> Control { Control::Continue }
+    fn visit_c_string(&mut self, _: &'ast CString) -> Control { Control::Continue }
     fn visit_call(&mut self, _: &'ast Call) -> Control { Control::Continue }
     fn visit_character(&mut self, _: &'ast Character) -> Control { Control::Continue }
     fn visit_closure(&mut self, _: &'ast Closure) -> Control { Control::Continue }
     fn visit_closure_arg(&mut self, _: &'ast ClosureArg) -> Control { Control::Continue }
     fn visit_comment(&mut self, _: &'ast Comment) -> Control { Control::Continue }
     fn visit_const(&mut self, _: &'ast Const) -> Control { Control::Continue }
+    fn visit_const_block(&mut self, _: &'ast ConstBlock) -> Control { Control::Continue }
     fn visit_continue(&mut self, _: &'ast Continue) -> Control { Control::Continue }
     fn visit_crate(&mut self, _: &'ast Crate) -> Control { Control::Continue }
     fn visit_dereference(&mut self, _: &'ast Dereference) -> Control { Control::Continue }
@@ -175,12 +178,14 @@ pub trait Visitor<'ast> {
     fn visit_function(&mut self, _: &'ast Function) -> Control { Control::Continue }
     fn visit_function_header(&mut self, _: &'ast FunctionHeader) -> Control { Control::Continue }
     fn visit_function_qualifiers(&mut self, _: &'ast FunctionQualifiers) -> Control { Control::Continue }
+    fn visit_generic_associated_type(&mut self, _: &'ast GenericAssociatedType) -> Control { Control::Continue }
+    fn visit_generic_declaration(&mut self, _: &'ast GenericDeclaration) -> Control { Control::Continue }
+    fn visit_generic_declaration_const(&mut self, _: &'ast GenericDeclarationConst) -> Control { Control::Continue }
     fn visit_generic_declaration_lifetime(&mut self, _: &'ast GenericDeclarationLifetime) -> Control { Control::Continue }
     fn visit_generic_declaration_type(&mut self, _: &'ast GenericDeclarationType) -> Control { Control::Continue }
     fn visit_generic_declarations(&mut self, _: &'ast GenericDeclarations) -> Control { Control::Continue }
     fn visit_ident(&mut self, _: &'ast Ident) -> Control { Control::Continue }
     fn visit_if(&mut self, _: &'ast If) -> Control { Control::Continue }
-    fn visit_if_let(&mut self, _: &'ast IfLet) -> Control { Control::Continue }
     fn visit_impl(&mut self, _: &'ast Impl) -> Control { Control::Continue }
     fn visit_impl_const(&mut self, _: &'ast ImplConst) -> Control { Control::Continue }
     fn visit_impl_function(&mut self, _: &'ast ImplFunction) -> Control { Control::Continue }
@@ -191,14 +196,25 @@ pub trait Visitor<'ast> {
     fn visit_impl_of_trait_type(&mut self, _: &'ast ImplOfTraitType) -> Control { Control::Continue }
     fn visit_impl_type(&mut self, _: &'ast ImplType) -> Control { Control::Continue }
     fn visit_item(&mut self, _: &'ast Item) -> Control { Control::Continue }
+    fn visit_labeled_block(&mut self, _: &'ast LabeledBlock) -> Control { Control::Continue }
     fn visit_let(&mut self, _: &'ast Let) -> Control { Control::Continue }
     fn visit_lifetime(&mut self, _: &'ast Lifetime) -> Control { Control::Continue }
     fn visit_loop(&mut self, _: &'ast Loop) -> Control { Control::Continue }
     fn visit_macro_call(&mut self, _: &'ast MacroCall) -> Control { Control::Continue }
     fn visit_macro_call_args(&mut self, _: &'ast MacroCallArgs) -> Control { Control::Continue }
+    fn visit_macro_fragment(&mut self, _: &'ast MacroFragment) -> Control { Control::Continue }
+    fn visit_macro_match(&mut self, _: &'ast MacroMatch) -> Control { Control::Continue }
+    fn visit_macro_matcher(&mut self, _: &'ast MacroMatcher) -> Control { Control::Continue }
+    fn visit_macro_repetition(&mut self, _: &'ast MacroRepetition) -> Control { Control::Continue }
+    fn visit_macro_rule(&mut self, _: &'ast MacroRule) -> Control { Control::Continue }
+    fn visit_macro_rules(&mut self, _: &'ast MacroRules) -> Control { Control::Continue }
     fn visit_match(&mut self, _: &'ast Match) -> Control { Control::Continue }
     fn visit_match_arm(&mut self, _: &'ast MatchArm) -> Control { Control::Continue }
     fn visit_match_hand(&mut self, _: &'ast MatchHand) -> Control { Control::Continue }
+    fn visit_meta(&mut self, _: &'ast Meta) -> Control { Control::Continue }
+    fn visit_meta_list(&mut self, _: &'ast MetaList) -> Control { Control::Continue }
+    fn visit_meta_list_member(&mut self, _: &'ast MetaListMember) -> Control { Control::Continue }
+    fn visit_meta_name_value(&mut self, _: &'ast MetaNameValue) -> Control { Control::Continue }
     fn visit_module(&mut self, _: &'ast Module) -> Control { Control::Continue }
     fn visit_named_argument(&mut self, _: &'ast NamedArgument) -> Control { Control::Continue }
     fn visit_number(&mut self, _: &'ast Number) -> Control { Control::Continue }
@@ -212,15 +228,19 @@ pub trait Visitor<'ast> {
     fn visit_path_component(&mut self, _: &'ast PathComponent) -> Control { Control::Continue }
     fn visit_pathed_ident(&mut self, _: &'ast PathedIdent) -> Control { Control::Continue }
     fn visit_pattern(&mut self, _: &'ast Pattern) -> Control { Control::Continue }
+    fn visit_pattern_alternation(&mut self, _: &'ast PatternAlternation) -> Control { Control::Continue }
     fn visit_pattern_box(&mut self, _: &'ast PatternBox) -> Control { Control::Continue }
     fn visit_pattern_byte(&mut self, _: &'ast PatternByte) -> Control { Control::Continue }
     fn visit_pattern_byte_string(&mut self, _: &'ast PatternByteString) -> Control { Control::Continue }
+    fn visit_pattern_c_string(&mut self, _: &'ast PatternCString) -> Control { Control::Continue }
     fn visit_pattern_character(&mut self, _: &'ast PatternCharacter) -> Control { Control::Continue }
+    fn visit_pattern_const_block(&mut self, _: &'ast PatternConstBlock) -> Control { Control::Continue }
     fn visit_pattern_ident(&mut self, _: &'ast PatternIdent) -> Control { Control::Continue }
     fn visit_pattern_kind(&mut self, _: &'ast PatternKind) -> Control { Control::Continue }
     fn visit_pattern_macro_call(&mut self, _: &'ast PatternMacroCall) -> Control { Control::Continue }
     fn visit_pattern_name(&mut self, _: &'ast PatternName) -> Control { Control::Continue }
     fn visit_pattern_number(&mut self, _: &'ast PatternNumber) -> Control { Control::Continue }
+    fn visit_pattern_parenthesized(&mut self, _: &'ast PatternParenthesized) -> Control { Control::Continue }
     fn visit_pattern_range_component(&mut self, _: &'ast PatternRangeComponent) -> Control { Control::Continue }
     fn visit_pattern_range_exclusive(&mut self, _: &'ast PatternRangeExclusive) -> Control { Control::Continue }
     fn visit_pattern_range_inclusive(&mut self, _: &'ast PatternRangeInclusive) -> Control { Control::Continue }
@@ -255,7 +275,11 @@ pub trait Visitor<'ast> {
     fn visit_struct_definition_field_unnamed(&mut self, _: &'ast StructDefinitionFieldUnnamed) -> Control { Control::Continue }
     fn visit_struct_literal(&mut self, _: &'ast StructLiteral) -> Control { Control::Continue }
     fn visit_struct_literal_field(&mut self, _: &'ast StructLiteralField) -> Control { Control::Continue }
+    fn visit_token_tree(&mut self, _: &'ast TokenTree) -> Control { Control::Continue }
+    fn visit_token_tree_group(&mut self, _: &'ast TokenTreeGroup) -> Control { Control::Continue }
+    fn visit_token_tree_leaf(&mut self, _: &'ast TokenTreeLeaf) -> Control { Control::Continue }
     fn visit_trait(&mut self, _: &'ast Trait) -> Control { Control::Continue }
+    fn visit_trait_alias(&mut self, _: &'ast TraitAlias) -> Control { Control::Continue }
     fn visit_trait_bound(&mut self, _: &'ast TraitBound) -> Control { Control::Continue }
     fn visit_trait_bound_lifetime(&mut self, _: &'ast TraitBoundLifetime) -> Control { Control::Continue }
     fn visit_trait_bound_normal(&mut self, _: &'ast TraitBoundNormal) -> Control { Control::Continue }
@@ -313,7 +337,6 @@ pub trait Visitor<'ast> {
     fn visit_where_lifetime(&mut self, _: &'ast WhereLifetime) -> Control { Control::Continue }
     fn visit_where_type(&mut self, _: &'ast WhereType) -> Control { Control::Continue }
     fn visit_while(&mut self, _: &'ast While) -> Control { Control::Continue }
-    fn visit_while_let(&mut self, _: &'ast WhileLet) -> Control { Control::Continue }
     fn visit_whitespace(&mut self, _: &'ast Whitespace) -> Control { Control::Continue }
 
     fn exit_argument(&mut self, _: &'ast Argument) {}
@@ -323,6 +346,7 @@ pub trait Visitor<'ast> {
     fn exit_as_type(&mut self, _: &'ast AsType) {}
     fn exit_ascription(&mut self, _: &'ast Ascription) {}
     fn exit_associated_type(&mut self, _: &'ast AssociatedType) {}
+    fn exit_associated_type_bound(&mut self, _: &'ast AssociatedTypeBound) {}
     fn exit_async_block(&mut self, _: &'ast AsyncBlock) {}
     fn exit_attribute(&mut self, _: &'ast Attribute) {}
     fn exit_attribute_literal(&mut self, _: &'ast AttributeLiteral) {}
@@ -331,24 +355,26 @@ pub trait Visitor<'ast> {
     fn exit_attributed_enum_variant(&mut self, _: &'ast Attributed<EnumVariant>) {}
     fn exit_attributed_expression(&mut self, _: &'ast Attributed<Expression>) {}
     fn exit_attributed_extern_block_member(&mut self, _: &'ast Attributed<ExternBlockMember>) {}
-    fn exit_attributed_generic_declaration_lifetime(&mut self, _: &'ast Attributed<GenericDeclarationLifetime>) {}
-    fn exit_attributed_generic_declaration_type(&mut self, _: &'ast Attributed<GenericDeclarationType>) {}
+    fn exit_attributed_generic_declaration(&mut self, _: &'ast Attributed<GenericDeclaration>) {}
     fn exit_attributed_impl_member(&mut self, _: &'ast Attributed<ImplMember>) {}
     fn exit_attributed_item(&mut self, _: &'ast Attributed<Item>) {}
     fn exit_attributed_struct_definition_field_named(&mut self, _: &'ast Attributed<StructDefinitionFieldNamed>) {}
     fn exit_attributed_struct_definition_field_unnamed(&mut self, _: &'ast Attributed<StructDefinitionFieldUnnamed>) {}
     fn exit_attributed_trait_member(&mut self, _: &'ast Attributed<TraitMember>) {}
+    fn exit_await(&mut self, _: &'ast Await) {}
     fn exit_binary(&mut self, _: &'ast Binary) {}
     fn exit_block(&mut self, _: &'ast Block) {}
     fn exit_break(&mut self, _: &'ast Break) {}
     fn exit_byte(&mut self, _: &'ast Byte) {}
     fn exit_byte_string(&mut self, _: &'ast ByteString) {}
+    fn exit_c_string(&mut self, _: &'ast CString) {}
     fn exit_call(&mut self, _: &'ast Call) {}
     fn exit_character(&mut self, _: &'ast Character) {}
     fn exit_closure(&mut self, _: &'ast Closure) {}
     fn exit_closure_arg(&mut self, _: &'ast ClosureArg) {}
     fn exit_comment(&mut self, _: &'ast Comment) {}
     fn exit_const(&mut self, _: &'ast Const) {}
+    fn exit_const_block(&mut self, _: &'ast ConstBlock) {}
     fn exit_continue(&mut self, _: &'ast Continue) {}
     fn exit_crate(&mut self, _: &'ast Crate) {}
     fn exit_dereference(&mut self, _: &'ast Dereference) {}
@@ -373,12 +399,14 @@ pub trait Visitor<'ast> {
     fn exit_function(&mut self, _: &'ast Function) {}
     fn exit_function_header(&mut self, _: &'ast FunctionHeader) {}
     fn exit_function_qualifiers(&mut self, _: &'ast FunctionQualifiers) {}
+    fn exit_generic_associated_type(&mut self, _: &'ast GenericAssociatedType) {}
+    fn exit_generic_declaration(&mut self, _: &'ast GenericDeclaration) {}
+    fn exit_generic_declaration_const(&mut self, _: &'ast GenericDeclarationConst) {}
     fn exit_generic_declaration_lifetime(&mut self, _: &'ast GenericDeclarationLifetime) {}
     fn exit_generic_declaration_type(&mut self, _: &'ast GenericDeclarationType) {}
     fn exit_generic_declarations(&mut self, _: &'ast GenericDeclarations) {}
     fn exit_ident(&mut self, _: &'ast Ident) {}
     fn exit_if(&mut self, _: &'ast If) {}
-    fn exit_if_let(&mut self, _: &'ast IfLet) {}
     fn exit_impl(&mut self, _: &'ast Impl) {}
     fn exit_impl_const(&mut self, _: &'ast ImplConst) {}
     fn exit_impl_function(&mut self, _: &'ast ImplFunction) {}
@@ -389,14 +417,25 @@ pub trait Visitor<'ast> {
     fn exit_impl_of_trait_type(&mut self, _: &'ast ImplOfTraitType) {}
     fn exit_impl_type(&mut self, _: &'ast ImplType) {}
     fn exit_item(&mut self, _: &'ast Item) {}
+    fn exit_labeled_block(&mut self, _: &'ast LabeledBlock) {}
     fn exit_let(&mut self, _: &'ast Let) {}
     fn exit_lifetime(&mut self, _: &'ast Lifetime) {}
     fn exit_loop(&mut self, _: &'ast Loop) {}
     fn exit_macro_call(&mut self, _: &'ast MacroCall) {}
     fn exit_macro_call_args(&mut self, _: &'ast MacroCallArgs) {}
+    fn exit_macro_fragment(&mut self, _: &'ast MacroFragment) {}
+    fn exit_macro_match(&mut self, _: &'ast MacroMatch) {}
+    fn exit_macro_matcher(&mut self, _: &'ast MacroMatcher) {}
+    fn exit_macro_repetition(&mut self, _: &'ast MacroRepetition) {}
+    fn exit_macro_rule(&mut self, _: &'ast MacroRule) {}
+    fn exit_macro_rules(&mut self, _: &'ast MacroRules) {}
     fn exit_match(&mut self, _: &'ast Match) {}
     fn exit_match_arm(&mut self, _: &'ast MatchArm) {}
     fn exit_match_hand(&mut self, _: &'ast MatchHand) {}
+    fn exit_meta(&mut self, _: &'ast Meta) {}
+    fn exit_meta_list(&mut self, _: &'ast MetaList) {}
+    fn exit_meta_list_member(&mut self, _: &'ast MetaListMember) {}
+    fn exit_meta_name_value(&mut self, _: &'ast MetaNameValue) {}
     fn exit_module(&mut self, _: &'ast Module) {}
     fn exit_named_argument(&mut self, _: &'ast NamedArgument) {}
     fn exit_number(&mut self, _: &'ast Number) {}
@@ -410,15 +449,19 @@ pub trait Visitor<'ast> {
     fn exit_path_component(&mut self, _: &'ast PathComponent) {}
     fn exit_pathed_ident(&mut self, _: &'ast PathedIdent) {}
     fn exit_pattern(&mut self, _: &'ast Pattern) {}
+    fn exit_pattern_alternation(&mut self, _: &'ast PatternAlternation) {}
     fn exit_pattern_box(&mut self, _: &'ast PatternBox) {}
     fn exit_pattern_byte(&mut self, _: &'ast PatternByte) {}
     fn exit_pattern_byte_string(&mut self, _: &'ast PatternByteString) {}
+    fn exit_pattern_c_string(&mut self, _: &'ast PatternCString) {}
     fn exit_pattern_character(&mut self, _: &'ast PatternCharacter) {}
+    fn exit_pattern_const_block(&mut self, _: &'ast PatternConstBlock) {}
     fn exit_pattern_ident(&mut self, _: &'ast PatternIdent) {}
     fn exit_pattern_kind(&mut self, _: &'ast PatternKind) {}
     fn exit_pattern_macro_call(&mut self, _: &'ast PatternMacroCall) {}
     fn exit_pattern_name(&mut self, _: &'ast PatternName) {}
     fn exit_pattern_number(&mut self, _: &'ast PatternNumber) {}
+    fn exit_pattern_parenthesized(&mut self, _: &'ast PatternParenthesized) {}
     fn exit_pattern_range_component(&mut self, _: &'ast PatternRangeComponent) {}
     fn exit_pattern_range_exclusive(&mut self, _: &'ast PatternRangeExclusive) {}
     fn exit_pattern_range_inclusive(&mut self, _: &'ast PatternRangeInclusive) {}
@@ -453,7 +496,11 @@ pub trait Visitor<'ast> {
     fn exit_struct_definition_field_unnamed(&mut self, _: &'ast StructDefinitionFieldUnnamed) {}
     fn exit_struct_literal(&mut self, _: &'ast StructLiteral) {}
     fn exit_struct_literal_field(&mut self, _: &'ast StructLiteralField) {}
+    fn exit_token_tree(&mut self, _: &'ast TokenTree) {}
+    fn exit_token_tree_group(&mut self, _: &'ast TokenTreeGroup) {}
+    fn exit_token_tree_leaf(&mut self, _: &'ast TokenTreeLeaf) {}
     fn exit_trait(&mut self, _: &'ast Trait) {}
+    fn exit_trait_alias(&mut self, _: &'ast TraitAlias) {}
     fn exit_trait_bound(&mut self, _: &'ast TraitBound) {}
     fn exit_trait_bound_lifetime(&mut self, _: &'ast TraitBoundLifetime) {}
     fn exit_trait_bound_normal(&mut self, _: &'ast TraitBoundNormal) {}
@@ -511,7 +558,6 @@ pub trait Visitor<'ast> {
     fn exit_where_lifetime(&mut self, _: &'ast WhereLifetime) {}
     fn exit_where_type(&mut self, _: &'ast WhereType) {}
     fn exit_while(&mut self, _: &'ast While) {}
-    fn exit_while_let(&mut self, _: &'ast WhileLet) {}
     fn exit_whitespace(&mut self, _: &'ast Whitespace) {}
 }
 
@@ -530,6 +576,7 @@ pub trait VisitorMut {
     fn visit_as_type(&mut self, _: &mut AsType) -> Control { Control::Continue }
     fn visit_ascription(&mut self, _: &mut Ascription) -> Control { Control::Continue }
     fn visit_associated_type(&mut self, _: &mut AssociatedType) -> Control { Control::Continue }
+    fn visit_associated_type_bound(&mut self, _: &mut AssociatedTypeBound) -> Control { Control::Continue }
     fn visit_async_block(&mut self, _: &mut AsyncBlock) -> Control { Control::Continue }
     fn visit_attribute(&mut self, _: &mut Attribute) -> Control { Control::Continue }
     fn visit_attribute_literal(&mut self, _: &mut AttributeLiteral) -> Control { Control::Continue }
@@ -538,24 +585,26 @@ pub trait VisitorMut {
     fn visit_attributed_enum_variant(&mut self, _: &mut Attributed<EnumVariant>) -> Control { Control::Continue }
     fn visit_attributed_expression(&mut self, _: &mut Attributed<Expression>) -> Control { Control::Continue }
     fn visit_attributed_extern_block_member(&mut self, _: &mut Attributed<ExternBlockMember>) -> Control { Control::Continue }
-    fn visit_attributed_generic_declaration_lifetime(&mut self, _: &mut Attributed<GenericDeclarationLifetime>) -> Control { Control::Continue }
-    fn visit_attributed_generic_declaration_type(&mut self, _: &mut Attributed<GenericDeclarationType>) -> Control { Control::Continue }
+    fn visit_attributed_generic_declaration(&mut self, _: &mut Attributed<GenericDeclaration>) -> Control { Control::Continue }
     fn visit_attributed_impl_member(&mut self, _: &mut Attributed<ImplMember>) -> Control { Control::Continue }
     fn visit_attributed_item(&mut self, _: &mut Attributed<Item>) -> Control { Control::Continue }
     fn visit_attributed_struct_definition_field_named(&mut self, _: &mut Attributed<StructDefinitionFieldNamed>) -> Control { Control::Continue }
     fn visit_attributed_struct_definition_field_unnamed(&mut self, _: &mut Attributed<StructDefinitionFieldUnnamed>) -> Control { Control::Continue }
     fn visit_attributed_trait_member(&mut self, _: &mut Attributed<TraitMember>) -> Control { Control::Continue }
+    fn visit_await(&mut self, _: &mut Await) -> Control { Control::Continue }
     fn visit_binary(&mut self, _: &mut Binary) -> Control { Control::Continue }
     fn visit_block(&mut self, _: &mut Block) -> Control { Control::Continue }
     fn visit_break(&mut self, _: &mut Break) -> Control { Control::Continue }
     fn visit_byte(&mut self, _: &mut Byte) -> Control { Control::Continue }
     fn visit_byte_string(&mut self, _: &mut ByteString) -> Control { Control::Continue }
+    fn visit_c_string(&mut self, _: &mut CString) -> Control { Control::Continue }
     fn visit_call(&mut self, _: &mut Call) -> Control { Control::Continue }
     fn visit_character(&mut self, _: &mut Character) -> Control { Control::Continue }
     fn visit_closure(&mut self, _: &mut Closure) -> Control { Control::Continue }
     fn visit_closure_arg(&mut self, _: &mut ClosureArg) -> Control { Control::Continue }
     fn visit_comment(&mut self, _: &mut Comment) -> Control { Control::Continue }
     fn visit_const(&mut self, _: &mut Const) -> Control { Control::Continue }
+    fn visit_const_block(&mut self, _: &mut ConstBlock) -> Control { Control::Continue }
     fn visit_continue(&mut self, _: &mut Continue) -> Control { Control::Continue }
     fn visit_crate(&mut self, _: &mut Crate) -> Control { Control::Continue }
     fn visit_dereference(&mut self, _: &mut Dereference) -> Control { Control::Continue }
@@ -580,12 +629,14 @@ pub trait VisitorMut {
     fn visit_function(&mut self, _: &mut Function) -> Control { Control::Continue }
     fn visit_function_header(&mut self, _: &mut FunctionHeader) -> Control { Control::Continue }
     fn visit_function_qualifiers(&mut self, _: &mut FunctionQualifiers) -> Control { Control::Continue }
+    fn visit_generic_associated_type(&mut self, _: &mut GenericAssociatedType) -> Control { Control::Continue }
+    fn visit_generic_declaration(&mut self, _: &mut GenericDeclaration) -> Control { Control::Continue }
+    fn visit_generic_declaration_const(&mut self, _: &mut GenericDeclarationConst) -> Control { Control::Continue }
     fn visit_generic_declaration_lifetime(&mut self, _: &mut GenericDeclarationLifetime) -> Control { Control::Continue }
     fn visit_generic_declaration_type(&mut self, _: &mut GenericDeclarationType) -> Control { Control::Continue }
     fn visit_generic_declarations(&mut self, _: &mut GenericDeclarations) -> Control { Control::Continue }
     fn visit_ident(&mut self, _: &mut Ident) -> Control { Control::Continue }
     fn visit_if(&mut self, _: &mut If) -> Control { Control::Continue }
-    fn visit_if_let(&mut self, _: &mut IfLet) -> Control { Control::Continue }
     fn visit_impl(&mut self, _: &mut Impl) -> Control { Control::Continue }
     fn visit_impl_const(&mut self, _: &mut ImplConst) -> Control { Control::Continue }
     fn visit_impl_function(&mut self, _: &mut ImplFunction) -> Control { Control::Continue }
@@ -596,14 +647,25 @@ pub trait VisitorMut {
     fn visit_impl_of_trait_type(&mut self, _: &mut ImplOfTraitType) -> Control { Control::Continue }
     fn visit_impl_type(&mut self, _: &mut ImplType) -> Control { Control::Continue }
     fn visit_item(&mut self, _: &mut Item) -> Control { Control::Continue }
+    fn visit_labeled_block(&mut self, _: &mut LabeledBlock) -> Control { Control::Continue }
     fn visit_let(&mut self, _: &mut Let) -> Control { Control::Continue }
     fn visit_lifetime(&mut self, _: &mut Lifetime) -> Control { Control::Continue }
     fn visit_loop(&mut self, _: &mut Loop) -> Control { Control::Continue }
     fn visit_macro_call(&mut self, _: &mut MacroCall) -> Control { Control::Continue }
     fn visit_macro_call_args(&mut self, _: &mut MacroCallArgs) -> Control { Control::Continue }
+    fn visit_macro_fragment(&mut self, _: &mut MacroFragment) -> Control { Control::Continue }
+    fn visit_macro_match(&mut self, _: &mut MacroMatch) -> Control { Control::Continue }
+    fn visit_macro_matcher(&mut self, _: &mut MacroMatcher) -> Control { Control::Continue }
+    fn visit_macro_repetition(&mut self, _: &mut MacroRepetition) -> Control { Control::Continue }
+    fn visit_macro_rule(&mut self, _: &mut MacroRule) -> Control { Control::Continue }
+    fn visit_macro_rules(&mut self, _: &mut MacroRules) -> Control { Control::Continue }
     fn visit_match(&mut self, _: &mut Match) -> Control { Control::Continue }
     fn visit_match_arm(&mut self, _: &mut MatchArm) -> Control { Control::Continue }
     fn visit_match_hand(&mut self, _: &mut MatchHand) -> Control { Control::Continue }
+    fn visit_meta(&mut self, _: &mut Meta) -> Control { Control::Continue }
+    fn visit_meta_list(&mut self, _: &mut MetaList) -> Control { Control::Continue }
+    fn visit_meta_list_member(&mut self, _: &mut MetaListMember) -> Control { Control::Continue }
+    fn visit_meta_name_value(&mut self, _: &mut MetaNameValue) -> Control { Control::Continue }
     fn visit_module(&mut self, _: &mut Module) -> Control { Control::Continue }
     fn visit_named_argument(&mut self, _: &mut NamedArgument) -> Control { Control::Continue }
     fn visit_number(&mut self, _: &mut Number) -> Control { Control::Continue }
@@ -617,15 +679,19 @@ pub trait VisitorMut {
     fn visit_path_component(&mut self, _: &mut PathComponent) -> Control { Control::Continue }
     fn visit_pathed_ident(&mut self, _: &mut PathedIdent) -> Control { Control::Continue }
     fn visit_pattern(&mut self, _: &mut Pattern) -> Control { Control::Continue }
+    fn visit_pattern_alternation(&mut self, _: &mut PatternAlternation) -> Control { Control::Continue }
     fn visit_pattern_box(&mut self, _: &mut PatternBox) -> Control { Control::Continue }
     fn visit_pattern_byte(&mut self, _: &mut PatternByte) -> Control { Control::Continue }
     fn visit_pattern_byte_string(&mut self, _: &mut PatternByteString) -> Control { Control::Continue }
+    fn visit_pattern_c_string(&mut self, _: &mut PatternCString) -> Control { Control::Continue }
     fn visit_pattern_character(&mut self, _: &mut PatternCharacter) -> Control { Control::Continue }
+    fn visit_pattern_const_block(&mut self, _: &mut PatternConstBlock) -> Control { Control::Continue }
     fn visit_pattern_ident(&mut self, _: &mut PatternIdent) -> Control { Control::Continue }
     fn visit_pattern_kind(&mut self, _: &mut PatternKind) -> Control { Control::Continue }
     fn visit_pattern_macro_call(&mut self, _: &mut PatternMacroCall) -> Control { Control::Continue }
     fn visit_pattern_name(&mut self, _: &mut PatternName) -> Control { Control::Continue }
     fn visit_pattern_number(&mut self, _: &mut PatternNumber) -> Control { Control::Continue }
+    fn visit_pattern_parenthesized(&mut self, _: &mut PatternParenthesized) -> Control { Control::Continue }
     fn visit_pattern_range_component(&mut self, _: &mut PatternRangeComponent) -> Control { Control::Continue }
     fn visit_pattern_range_exclusive(&mut self, _: &mut PatternRangeExclusive) -> Control { Control::Continue }
     fn visit_pattern_range_inclusive(&mut self, _: &mut PatternRangeInclusive) -> Control { Control::Continue }
@@ -660,7 +726,11 @@ pub trait VisitorMut {
     fn visit_struct_definition_field_unnamed(&mut self, _: &mut StructDefinitionFieldUnnamed) -> Control { Control::Continue }
     fn visit_struct_literal(&mut self, _: &mut StructLiteral) -> Control { Control::Continue }
     fn visit_struct_literal_field(&mut self, _: &mut StructLiteralField) -> Control { Control::Continue }
+    fn visit_token_tree(&mut self, _: &mut TokenTree) -> Control { Control::Continue }
+    fn visit_token_tree_group(&mut self, _: &mut TokenTreeGroup) -> Control { Control::Continue }
+    fn visit_token_tree_leaf(&mut self, _: &mut TokenTreeLeaf) -> Control { Control::Continue }
     fn visit_trait(&mut self, _: &mut Trait) -> Control { Control::Continue }
+    fn visit_trait_alias(&mut self, _: &mut TraitAlias) -> Control { Control::Continue }
     fn visit_trait_bound(&mut self, _: &mut TraitBound) -> Control { Control::Continue }
     fn visit_trait_bound_lifetime(&mut self, _: &mut TraitBoundLifetime) -> Control { Control::Continue }
     fn visit_trait_bound_normal(&mut self, _: &mut TraitBoundNormal) -> Control { Control::Continue }
@@ -718,7 +788,6 @@ pub trait VisitorMut {
     fn visit_where_lifetime(&mut self, _: &mut WhereLifetime) -> Control { Control::Continue }
     fn visit_where_type(&mut self, _: &mut WhereType) -> Control { Control::Continue }
     fn visit_while(&mut self, _: &mut While) -> Control { Control::Continue }
-    fn visit_while_let(&mut self, _: &mut WhileLet) -> Control { Control::Continue }
     fn visit_whitespace(&mut self, _: &mut Whitespace) -> Control { Control::Continue }
 
     fn exit_argument(&mut self, _: &mut Argument) {}
@@ -728,6 +797,7 @@ pub trait VisitorMut {
     fn exit_as_type(&mut self, _: &mut AsType) {}
     fn exit_ascription(&mut self, _: &mut Ascription) {}
     fn exit_associated_type(&mut self, _: &mut AssociatedType) {}
+    fn exit_associated_type_bound(&mut self, _: &mut AssociatedTypeBound) {}
     fn exit_async_block(&mut self, _: &mut AsyncBlock) {}
     fn exit_attribute(&mut self, _: &mut Attribute) {}
     fn exit_attribute_literal(&mut self, _: &mut AttributeLiteral) {}
@@ -736,24 +806,26 @@ pub trait VisitorMut {
     fn exit_attributed_enum_variant(&mut self, _: &mut Attributed<EnumVariant>) {}
     fn exit_attributed_expression(&mut self, _: &mut Attributed<Expression>) {}
     fn exit_attributed_extern_block_member(&mut self, _: &mut Attributed<ExternBlockMember>) {}
-    fn exit_attributed_generic_declaration_lifetime(&mut self, _: &mut Attributed<GenericDeclarationLifetime>) {}
-    fn exit_attributed_generic_declaration_type(&mut self, _: &mut Attributed<GenericDeclarationType>) {}
+    fn exit_attributed_generic_declaration(&mut self, _: &mut Attributed<GenericDeclaration>) {}
     fn exit_attributed_impl_member(&mut self, _: &mut Attributed<ImplMember>) {}
     fn exit_attributed_item(&mut self, _: &mut Attributed<Item>) {}
     fn exit_attributed_struct_definition_field_named(&mut self, _: &mut Attributed<StructDefinitionFieldNamed>) {}
     fn exit_attributed_struct_definition_field_unnamed(&mut self, _: &mut Attributed<StructDefinitionFieldUnnamed>) {}
     fn exit_attributed_trait_member(&mut self, _: &mut Attributed<TraitMember>) {}
+    fn exit_await(&mut self, _: &mut Await) {}
     fn exit_binary(&mut self, _: &mut Binary) {}
     fn exit_block(&mut self, _: &mut Block) {}
     fn exit_break(&mut self, _: &mut Break) {}
     fn exit_byte(&mut self, _: &mut Byte) {}
     fn exit_byte_string(&mut self, _: &mut ByteString) {}
+    fn exit_c_string(&mut self, _: &mut CString) {}
     fn exit_call(&mut self, _: &mut Call) {}
     fn exit_character(&mut self, _: &mut Character) {}
     fn exit_closure(&mut self, _: &mut Closure) {}
     fn exit_closure_arg(&mut self, _: &mut ClosureArg) {}
     fn exit_comment(&mut self, _: &mut Comment) {}
     fn exit_const(&mut self, _: &mut Const) {}
+    fn exit_const_block(&mut self, _: &mut ConstBlock) {}
     fn exit_continue(&mut self, _: &mut Continue) {}
     fn exit_crate(&mut self, _: &mut Crate) {}
     fn exit_dereference(&mut self, _: &mut Dereference) {}
@@ -778,12 +850,14 @@ pub trait VisitorMut {
     fn exit_function(&mut self, _: &mut Function) {}
     fn exit_function_header(&mut self, _: &mut FunctionHeader) {}
     fn exit_function_qualifiers(&mut self, _: &mut FunctionQualifiers) {}
+    fn exit_generic_associated_type(&mut self, _: &mut GenericAssociatedType) {}
+    fn exit_generic_declaration(&mut self, _: &mut GenericDeclaration) {}
+    fn exit_generic_declaration_const(&mut self, _: &mut GenericDeclarationConst) {}
     fn exit_generic_declaration_lifetime(&mut self, _: &mut GenericDeclarationLifetime) {}
     fn exit_generic_declaration_type(&mut self, _: &mut GenericDeclarationType) {}
     fn exit_generic_declarations(&mut self, _: &mut GenericDeclarations) {}
     fn exit_ident(&mut self, _: &mut Ident) {}
     fn exit_if(&mut self, _: &mut If) {}
-    fn exit_if_let(&mut self, _: &mut IfLet) {}
     fn exit_impl(&mut self, _: &mut Impl) {}
     fn exit_impl_const(&mut self, _: &mut ImplConst) {}
     fn exit_impl_function(&mut self, _: &mut ImplFunction) {}
@@ -794,14 +868,25 @@ pub trait VisitorMut {
     fn exit_impl_of_trait_type(&mut self, _: &mut ImplOfTraitType) {}
     fn exit_impl_type(&mut self, _: &mut ImplType) {}
     fn exit_item(&mut self, _: &mut Item) {}
+    fn exit_labeled_block(&mut self, _: &mut LabeledBlock) {}
     fn exit_let(&mut self, _: &mut Let) {}
     fn exit_lifetime(&mut self, _: &mut Lifetime) {}
     fn exit_loop(&mut self, _: &mut Loop) {}
     fn exit_macro_call(&mut self, _: &mut MacroCall) {}
     fn exit_macro_call_args(&mut self, _: &mut MacroCallArgs) {}
+    fn exit_macro_fragment(&mut self, _: &mut MacroFragment) {}
+    fn exit_macro_match(&mut self, _: &mut MacroMatch) {}
+    fn exit_macro_matcher(&mut self, _: &mut MacroMatcher) {}
+    fn exit_macro_repetition(&mut self, _: &mut MacroRepetition) {}
+    fn exit_macro_rule(&mut self, _: &mut MacroRule) {}
+    fn exit_macro_rules(&mut self, _: &mut MacroRules) {}
     fn exit_match(&mut self, _: &mut Match) {}
     fn exit_match_arm(&mut self, _: &mut MatchArm) {}
     fn exit_match_hand(&mut self, _: &mut MatchHand) {}
+    fn exit_meta(&mut self, _: &mut Meta) {}
+    fn exit_meta_list(&mut self, _: &mut MetaList) {}
+    fn exit_meta_list_member(&mut self, _: &mut MetaListMember) {}
+    fn exit_meta_name_value(&mut self, _: &mut MetaNameValue) {}
     fn exit_module(&mut self, _: &mut Module) {}
     fn exit_named_argument(&mut self, _: &mut NamedArgument) {}
     fn exit_number(&mut self, _: &mut Number) {}
@@ -815,15 +900,19 @@ pub trait VisitorMut {
     fn exit_path_component(&mut self, _: &mut PathComponent) {}
     fn exit_pathed_ident(&mut self, _: &mut PathedIdent) {}
     fn exit_pattern(&mut self, _: &mut Pattern) {}
+    fn exit_pattern_alternation(&mut self, _: &mut PatternAlternation) {}
     fn exit_pattern_box(&mut self, _: &mut PatternBox) {}
     fn exit_pattern_byte(&mut self, _: &mut PatternByte) {}
     fn exit_pattern_byte_string(&mut self, _: &mut PatternByteString) {}
+    fn exit_pattern_c_string(&mut self, _: &mut PatternCString) {}
     fn exit_pattern_character(&mut self, _: &mut PatternCharacter) {}
+    fn exit_pattern_const_block(&mut self, _: &mut PatternConstBlock) {}
     fn exit_pattern_ident(&mut self, _: &mut PatternIdent) {}
     fn exit_pattern_kind(&mut self, _: &mut PatternKind) {}
     fn exit_pattern_macro_call(&mut self, _: &mut PatternMacroCall) {}
     fn exit_pattern_name(&mut self, _: &mut PatternName) {}
     fn exit_pattern_number(&mut self, _: &mut PatternNumber) {}
+    fn exit_pattern_parenthesized(&mut self, _: &mut PatternParenthesized) {}
     fn exit_pattern_range_component(&mut self, _: &mut PatternRangeComponent) {}
     fn exit_pattern_range_exclusive(&mut self, _: &mut PatternRangeExclusive) {}
     fn exit_pattern_range_inclusive(&mut self, _: &mut PatternRangeInclusive) {}
@@ -858,7 +947,11 @@ pub trait VisitorMut {
     fn exit_struct_definition_field_unnamed(&mut self, _: &mut StructDefinitionFieldUnnamed) {}
     fn exit_struct_literal(&mut self, _: &mut StructLiteral) {}
     fn exit_struct_literal_field(&mut self, _: &mut StructLiteralField) {}
+    fn exit_token_tree(&mut self, _: &mut TokenTree) {}
+    fn exit_token_tree_group(&mut self, _: &mut TokenTreeGroup) {}
+    fn exit_token_tree_leaf(&mut self, _: &mut TokenTreeLeaf) {}
     fn exit_trait(&mut self, _: &mut Trait) {}
+    fn exit_trait_alias(&mut self, _: &mut TraitAlias) {}
     fn exit_trait_bound(&mut self, _: &mut TraitBound) {}
     fn exit_trait_bound_lifetime(&mut self, _: &mut TraitBoundLifetime) {}
     fn exit_trait_bound_normal(&mut self, _: &mut TraitBoundNormal) {}
@@ -916,6 +1009,5 @@ pub trait VisitorMut {
     fn exit_where_lifetime(&mut self, _: &mut WhereLifetime) {}
     fn exit_where_type(&mut self, _: &mut WhereType) {}
     fn exit_while(&mut self, _: &mut While) {}
-    fn exit_while_let(&mut self, _: &mut WhileLet) {}
     fn exit_whitespace(&mut self, _: &mut Whitespace) {}
 }
@@ -36,6 +36,11 @@ impl WhitespaceApportioner {
         self.0.is_empty()
     }
 
+    /// Discards whitespace that starts outside of the extent
+    pub fn retain_within(&mut self, extent: crate::Extent) {
+        self.0.retain(|&start, _| extent.0 <= start && start < extent.1);
+    }
+
     fn divvy_up(&mut self, node_extent: crate::Extent, whitespace: &mut Vec<Whitespace>) {
         // This could be optimized with the right data structure
         let contained_whitespace_keys: Vec<_> = self.0.range(node_extent.0..node_extent.1)
@@ -68,18 +73,19 @@ impl VisitorMut for WhitespaceApportioner {
         fn exit_as_type(&mut self, &mut AsType) {}
         fn exit_ascription(&mut self, &mut Ascription) {}
         fn exit_associated_type(&mut self, &mut AssociatedType) {}
+        fn exit_associated_type_bound(&mut self, &mut AssociatedTypeBound) {}
         fn exit_attribute_literal(&mut self, &mut AttributeLiteral) {}
         fn exit_attribute_containing_literal(&mut self, &mut AttributeContainingLiteral) {}
         fn exit_attributed_enum_variant(&mut self, &mut Attributed<EnumVariant>) {}
         fn exit_attributed_expression(&mut self, &mut Attributed<Expression>) {}
         fn exit_attributed_extern_block_member(&mut self, &mut Attributed<ExternBlockMember>) {}
-        fn exit_attributed_generic_declaration_lifetime(&mut self, &mut Attributed<GenericDeclarationLifetime>) {}
-        fn exit_attributed_generic_declaration_type(&mut self, &mut Attributed<GenericDeclarationType>) {}
+        fn exit_attributed_generic_declaration(&mut self, &mut Attributed<GenericDeclaration>) {}
         fn exit_attributed_impl_member(&mut self, &mut Attributed<ImplMember>) {}
         fn exit_attributed_item(&mut self, &mut Attributed<Item>) {}
         fn exit_attributed_struct_definition_field_named(&mut self, &mut Attributed<StructDefinitionFieldNamed>) {}
         fn exit_attributed_struct_definition_field_unnamed(&mut self, &mut Attributed<StructDefinitionFieldUnnamed>) {}
         fn exit_attributed_trait_member(&mut self, &mut Attributed<TraitMember>) {}
+        fn exit_await(&mut self, &mut Await) {}
         fn exit_binary(&mut self, &mut Binary) {}
         fn exit_block(&mut self, &mut Block) {}
         fn exit_break(&mut self, &mut Break) {}
@@ -87,6 +93,7 @@ impl VisitorMut for WhitespaceApportioner {
         fn exit_closure(&mut self, &mut Closure) {}
         fn exit_closure_arg(&mut self, &mut ClosureArg) {}
         fn exit_const(&mut self, &mut Const) {}
+        fn exit_const_block(&mut self, &mut ConstBlock) {}
         fn exit_continue(&mut self, &mut Continue) {}
         fn exit_crate(&mut self, &mut Crate) {}
         fn exit_dereference(&mut self, &mut Dereference) {}
@@ -104,22 +111,31 @@ impl VisitorMut for WhitespaceApportioner {
         fn exit_function(&mut self, &mut Function) {}
         fn exit_function_header(&mut self, &mut FunctionHeader) {}
         fn exit_function_qualifiers(&mut self, &mut FunctionQualifiers) {}
+        fn exit_generic_associated_type(&mut self, &mut GenericAssociatedType) {}
+        fn exit_generic_declaration_const(&mut self, &mut GenericDeclarationConst) {}
         fn exit_generic_declaration_lifetime(&mut self, &mut GenericDeclarationLifetime) {}
         fn exit_generic_declaration_type(&mut self, &mut GenericDeclarationType) {}
         fn exit_generic_declarations(&mut self, &mut GenericDeclarations) {}
         fn exit_if(&mut self, &mut If) {}
-        fn exit_if_let(&mut self, &mut IfLet) {}
         fn exit_impl(&mut self, &mut Impl) {}
         fn exit_impl_const(&mut self, &mut ImplConst) {}
         fn exit_impl_function(&mut self, &mut ImplFunction) {}
         fn exit_impl_of_inherent(&mut self, &mut ImplOfInherent) {}
         fn exit_impl_of_trait(&mut self, &mut ImplOfTrait) {}
         fn exit_impl_type(&mut self, &mut ImplType) {}
+        fn exit_labeled_block(&mut self, &mut LabeledBlock) {}
         fn exit_let(&mut self, &mut Let) {}
         fn exit_loop(&mut self, &mut Loop) {}
         fn exit_macro_call(&mut self, &mut MacroCall) {}
+        fn exit_macro_fragment(&mut self, &mut MacroFragment) {}
+        fn exit_macro_matcher(&mut self, &mut MacroMatcher) {}
+        fn exit_macro_repetition(&mut self, &mut MacroRepetition) {}
+        fn exit_macro_rule(&mut self, &mut MacroRule) {}
+        fn exit_macro_rules(&mut self, &mut MacroRules) {}
         fn exit_match(&mut self, &mut Match) {}
         fn exit_match_arm(&mut self, &mut MatchArm) {}
+        fn exit_meta_list(&mut self, &mut MetaList) {}
+        fn exit_meta_name_value(&mut self, &mut MetaNameValue) {}
         fn exit_module(&mut self, &mut Module) {}
         fn exit_named_argument(&mut self, &mut NamedArgument) {}
         fn exit_number(&mut self, &mut Number) {}
@@ -128,11 +144,13 @@ impl VisitorMut for WhitespaceApportioner {
         fn exit_path_component(&mut self, &mut PathComponent) {}
         fn exit_pathed_ident(&mut self, &mut PathedIdent) {}
         fn exit_pattern(&mut self, &mut Pattern) {}
+        fn exit_pattern_alternation(&mut self, &mut PatternAlternation) {}
         fn exit_pattern_box(&mut self, &mut PatternBox) {}
         fn exit_pattern_ident(&mut self, &mut PatternIdent) {}
         fn exit_pattern_macro_call(&mut self, &mut PatternMacroCall) {}
         fn exit_pattern_name(&mut self, &mut PatternName) {}
         fn exit_pattern_number(&mut self, &mut PatternNumber) {}
+        fn exit_pattern_parenthesized(&mut self, &mut PatternParenthesized) {}
         fn exit_pattern_range_exclusive(&mut self, &mut PatternRangeExclusive) {}
         fn exit_pattern_range_inclusive(&mut self, &mut PatternRangeInclusive) {}
         fn exit_pattern_reference(&mut self, &mut PatternReference) {}
@@ -158,6 +176,7 @@ impl VisitorMut for WhitespaceApportioner {
         fn exit_struct_literal(&mut self, &mut StructLiteral) {}
         fn exit_struct_literal_field(&mut self, &mut StructLiteralField) {}
         fn exit_trait(&mut self, &mut Trait) {}
+        fn exit_trait_alias(&mut self, &mut TraitAlias) {}
         fn exit_trait_bound_lifetime(&mut self, &mut TraitBoundLifetime) {}
         fn exit_trait_bound_normal(&mut self, &mut TraitBoundNormal) {}
         fn exit_trait_bound_relaxed(&mut self, &mut TraitBoundRelaxed) {}
@@ -200,7 +219,6 @@ impl VisitorMut for WhitespaceApportioner {
         fn exit_where_lifetime(&mut self, &mut WhereLifetime) {}
         fn exit_where_type(&mut self, &mut WhereType) {}
         fn exit_while(&mut self, &mut While) {}
-        fn exit_while_let(&mut self, &mut WhileLet) {}
     }
 
     // Everything else should fall into here